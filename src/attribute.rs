@@ -0,0 +1,349 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+/// Part of the code that the attributes of a table are parsed from, which is either a byte of text or a node other than text.
+struct Unit {
+    /// The byte, or `None` if the unit is a node other than text.
+    byte: Option<u8>,
+    end: usize,
+    is_comment: bool,
+    start: usize,
+}
+
+impl crate::TableAttribute<'_> {
+    /// Returns the class names of the attribute if it's a `class` attribute, and otherwise nothing. Class names next to code other than text in the value, such as templates, are left out, since the code can change them.
+    ///
+    /// ```
+    /// use parse_wiki_text_2::{Configuration, Node};
+    ///
+    /// let wiki_text = "{| class=\"wikitable sortable\"\n| colspan=2 | a\n|}";
+    /// let output = Configuration::default().parse(wiki_text).unwrap();
+    /// let Node::Table { attributes, rows, .. } = &output.nodes[0] else {
+    ///     unreachable!();
+    /// };
+    /// assert_eq!(attributes[0].class_names(), ["wikitable", "sortable"]);
+    /// let cell_attributes = rows[0].cells[0].attributes.as_ref().unwrap();
+    /// assert_eq!(cell_attributes[0].name, "colspan");
+    /// ```
+    #[must_use]
+    pub fn class_names(&self) -> Vec<&str> {
+        let Some(value) = &self.value else {
+            return vec![];
+        };
+        if !self.name.eq_ignore_ascii_case("class") {
+            return vec![];
+        }
+        let is_code = |node: Option<&crate::Node>| {
+            node.is_some_and(|node| !matches!(node, crate::Node::Text { .. }))
+        };
+        let mut class_names = vec![];
+        for (index, node) in value.nodes.iter().enumerate() {
+            if let crate::Node::Text { value: text, .. } = node {
+                let mut words: Vec<&str> = text.split_ascii_whitespace().collect();
+                if !text.ends_with(|character: char| character.is_ascii_whitespace())
+                    && is_code(value.nodes.get(index + 1))
+                {
+                    words.pop();
+                }
+                if !text.starts_with(|character: char| character.is_ascii_whitespace())
+                    && index > 0
+                    && is_code(value.nodes.get(index - 1))
+                    && !words.is_empty()
+                {
+                    words.remove(0);
+                }
+                class_names.extend(words);
+            }
+        }
+        class_names
+    }
+}
+
+pub fn parse_attributes(
+    wiki_text: &str,
+    start_position: usize,
+    end_position: usize,
+) -> Vec<crate::Attribute<'_>> {
+    let bytes = &wiki_text.as_bytes()[..end_position];
+    let mut attributes = vec![];
+    let mut position = start_position;
+    loop {
+        while let Some(b'\t' | b'\n' | b'\x0c' | b'\r' | b' ' | b'/') = bytes.get(position) {
+            position += 1;
+        }
+        if position >= end_position {
+            break;
+        }
+        let name_start_position = position;
+        while let Some(character) = bytes.get(position) {
+            match character {
+                b'\t' | b'\n' | b'\x0c' | b'\r' | b' ' | b'/' | b'=' | b'>' => break,
+                _ => position += 1,
+            }
+        }
+        if position == name_start_position {
+            // A stray `=` or `>` that can't start an attribute name.
+            position += 1;
+            continue;
+        }
+        let name = &wiki_text[name_start_position..position];
+        let name = if name.bytes().any(|character| character.is_ascii_uppercase()) {
+            name.to_ascii_lowercase().into()
+        } else {
+            crate::Cow::Borrowed(name)
+        };
+        let mut value_position = position;
+        while let Some(b'\t' | b'\n' | b'\x0c' | b'\r' | b' ') = bytes.get(value_position) {
+            value_position += 1;
+        }
+        if bytes.get(value_position) != Some(&b'=') {
+            attributes.push(crate::Attribute {
+                end: position,
+                name,
+                start: name_start_position,
+                value: None,
+            });
+            continue;
+        }
+        value_position += 1;
+        while let Some(b'\t' | b'\n' | b'\x0c' | b'\r' | b' ') = bytes.get(value_position) {
+            value_position += 1;
+        }
+        let (quote, value_start_position, value_end_position) = match bytes.get(value_position) {
+            Some(&quote_character @ (b'"' | b'\'')) => {
+                let value_start_position = value_position + 1;
+                let value_end_position = bytes[value_start_position..]
+                    .iter()
+                    .position(|&character| character == quote_character)
+                    .map_or(end_position, |length| value_start_position + length);
+                position = (value_end_position + 1).min(end_position);
+                (
+                    if quote_character == b'"' {
+                        crate::AttributeQuote::Double
+                    } else {
+                        crate::AttributeQuote::Single
+                    },
+                    value_start_position,
+                    value_end_position,
+                )
+            }
+            _ => {
+                let value_end_position = bytes[value_position..]
+                    .iter()
+                    .position(|character| {
+                        matches!(character, b'\t' | b'\n' | b'\x0c' | b'\r' | b' ' | b'>')
+                    })
+                    .map_or(end_position, |length| value_position + length);
+                position = value_end_position;
+                (
+                    crate::AttributeQuote::None,
+                    value_position,
+                    value_end_position,
+                )
+            }
+        };
+        attributes.push(crate::Attribute {
+            end: position,
+            name,
+            start: name_start_position,
+            value: Some(crate::AttributeValue {
+                end: value_end_position,
+                quote,
+                start: value_start_position,
+                value: crate::Cow::Borrowed(&wiki_text[value_start_position..value_end_position]),
+            }),
+        });
+    }
+    attributes
+}
+
+/// Parses the HTML attributes of a table, table caption, table row or table cell from the nodes of the code between the table syntax. Unlike attributes of tags, these attributes can contain templates and other code. Code in a value is kept as nodes of the value, and code in a name is kept as nodes of the name.
+pub fn parse_table_attributes<'a>(
+    wiki_text: &'a str,
+    nodes: Vec<crate::Node<'a>>,
+) -> Vec<crate::TableAttribute<'a>> {
+    let mut units = vec![];
+    for node in &nodes {
+        match node {
+            crate::Node::Text { start, value, .. } => {
+                units.extend(value.bytes().enumerate().map(|(index, byte)| Unit {
+                    byte: Some(byte),
+                    end: start + index + 1,
+                    is_comment: false,
+                    start: start + index,
+                }))
+            }
+            _ => units.push(Unit {
+                byte: None,
+                end: crate::Positioned::end(node),
+                is_comment: matches!(node, crate::Node::Comment { .. }),
+                start: crate::Positioned::start(node),
+            }),
+        }
+    }
+    let mut nodes: Vec<_> = nodes.into_iter().map(Some).collect();
+    let mut attributes = vec![];
+    let mut index = 0;
+    loop {
+        // Like in tags, a stray `=` or `>` where an attribute name is expected is skipped, and so is a comment, which stays in the code between the attributes.
+        while units.get(index).is_some_and(|unit| {
+            unit.is_comment
+                || matches!(
+                    unit.byte,
+                    Some(b'\t' | b'\n' | b'\x0c' | b'\r' | b' ' | b'/' | b'=' | b'>')
+                )
+        }) {
+            index += 1;
+        }
+        let Some(unit) = units.get(index) else {
+            break;
+        };
+        let start = unit.start;
+        let name_index = index;
+        while units.get(index).is_some_and(|unit| {
+            !matches!(
+                unit.byte,
+                Some(b'\t' | b'\n' | b'\x0c' | b'\r' | b' ' | b'/' | b'=' | b'>')
+            )
+        }) {
+            index += 1;
+        }
+        let name_end = units[index - 1].end;
+        let name = crate::Cow::Borrowed(&wiki_text[start..name_end]);
+        let name_nodes = if units[name_index..index]
+            .iter()
+            .any(|unit| unit.byte.is_none())
+        {
+            take_nodes(&mut nodes, start, name_end)
+        } else {
+            vec![]
+        };
+        let mut value_index = skip_whitespace(&units, index);
+        if units.get(value_index).and_then(|unit| unit.byte) != Some(b'=') {
+            attributes.push(crate::TableAttribute {
+                end: name_end,
+                name,
+                name_nodes,
+                start,
+                value: None,
+            });
+            continue;
+        }
+        value_index = skip_whitespace(&units, value_index + 1);
+        let (quote, value_start, value_end, end) = match units.get(value_index) {
+            None => {
+                let position = units[value_index - 1].end;
+                index = value_index;
+                (crate::AttributeQuote::None, position, position, position)
+            }
+            Some(
+                unit @ Unit {
+                    byte: Some(quote_character @ (b'"' | b'\'')),
+                    ..
+                },
+            ) => {
+                let closing_index = units[value_index + 1..]
+                    .iter()
+                    .position(|unit| unit.byte == Some(*quote_character))
+                    .map(|length| value_index + 1 + length);
+                let value_end = match closing_index {
+                    None => units[units.len() - 1].end,
+                    Some(closing_index) => units[closing_index].start,
+                };
+                index = closing_index.map_or(units.len(), |closing_index| closing_index + 1);
+                (
+                    if *quote_character == b'"' {
+                        crate::AttributeQuote::Double
+                    } else {
+                        crate::AttributeQuote::Single
+                    },
+                    unit.end,
+                    value_end,
+                    units[index - 1].end,
+                )
+            }
+            Some(unit) => {
+                index = value_index;
+                while units.get(index).is_some_and(|unit| {
+                    !matches!(unit.byte, Some(b'\t' | b'\n' | b'\x0c' | b'\r' | b' '))
+                }) {
+                    index += 1;
+                }
+                let value_end = units[index - 1].end;
+                (
+                    crate::AttributeQuote::None,
+                    unit.start,
+                    value_end,
+                    value_end,
+                )
+            }
+        };
+        attributes.push(crate::TableAttribute {
+            end,
+            name,
+            name_nodes,
+            start,
+            value: Some(crate::TableAttributeValue {
+                end: value_end,
+                nodes: take_nodes(&mut nodes, value_start, value_end),
+                quote,
+                start: value_start,
+            }),
+        });
+    }
+    attributes
+}
+
+fn skip_whitespace(units: &[Unit], mut index: usize) -> usize {
+    while let Some(Unit {
+        byte: Some(b'\t' | b'\n' | b'\x0c' | b'\r' | b' '),
+        ..
+    }) = units.get(index)
+    {
+        index += 1;
+    }
+    index
+}
+
+/// Takes the nodes between the given positions, with text cut at the positions.
+fn take_nodes<'a>(
+    nodes: &mut [Option<crate::Node<'a>>],
+    start: usize,
+    end: usize,
+) -> Vec<crate::Node<'a>> {
+    let mut taken = vec![];
+    for node in nodes {
+        match node {
+            Some(crate::Node::Text {
+                end: text_end,
+                start: text_start,
+                value,
+            }) => {
+                let (piece_start, piece_end) = (start.max(*text_start), end.min(*text_end));
+                crate::image::push_text(
+                    &mut taken,
+                    piece_start,
+                    piece_end,
+                    if piece_start < piece_end {
+                        crate::image::slice(
+                            value,
+                            piece_start - *text_start,
+                            piece_end - *text_start,
+                        )
+                    } else {
+                        crate::Cow::Borrowed("")
+                    },
+                );
+            }
+            Some(other)
+                if crate::Positioned::start(other) >= start
+                    && crate::Positioned::end(other) <= end =>
+            {
+                taken.extend(node.take());
+            }
+            _ => {}
+        }
+    }
+    taken
+}
@@ -3,7 +3,9 @@
 // the file LICENSE at the top-level directory of this distribution.
 
 pub fn parse_character_entity(state: &mut crate::State, configuration: &crate::Configuration) {
-    if let Ok((match_length, character)) = configuration
+    if state.get_byte(state.scan_position + 1) == Some(b'#') {
+        parse_numeric_character_reference(state);
+    } else if let Ok((match_length, character)) = configuration
         .character_entities
         .find(&state.wiki_text[state.scan_position + 1..])
     {
@@ -20,3 +22,77 @@ pub fn parse_character_entity(state: &mut crate::State, configuration: &crate::C
         state.scan_position += 1;
     }
 }
+
+/// Decodes the code of a character entity, a named entity or a numeric character reference, including the `&` and `;`.
+pub fn decode_character_entity(text: &str) -> Option<char> {
+    let name = text.strip_prefix('&')?.strip_suffix(';')?;
+    let Some(digits) = name.strip_prefix('#') else {
+        return crate::html_entities::HTML_ENTITIES
+            .binary_search_by_key(&name, |(name, _)| name)
+            .ok()
+            .map(|index| crate::html_entities::HTML_ENTITIES[index].1);
+    };
+    let code_point = match digits.strip_prefix(['X', 'x']) {
+        None => digits.parse().ok()?,
+        Some(digits) => u32::from_str_radix(digits, 16).ok()?,
+    };
+    if digits.starts_with(['+', '-']) || !is_valid_code_point(code_point) {
+        return None;
+    }
+    char::from_u32(code_point)
+}
+
+fn parse_numeric_character_reference(state: &mut crate::State) {
+    let start_position = state.scan_position;
+    let (radix, digits_start_position) = match state.get_byte(start_position + 2) {
+        Some(b'X' | b'x') => (16, start_position + 3),
+        _ => (10, start_position + 2),
+    };
+    let mut position = digits_start_position;
+    let mut code_point: Option<u32> = Some(0);
+    while let Some(digit) = state
+        .get_byte(position)
+        .and_then(|character| (character as char).to_digit(radix))
+    {
+        code_point = code_point
+            .and_then(|code_point| code_point.checked_mul(radix))
+            .and_then(|code_point| code_point.checked_add(digit));
+        position += 1;
+    }
+    if position == digits_start_position || state.get_byte(position) != Some(b';') {
+        state.scan_position += 1;
+        return;
+    }
+    let end_position = position + 1;
+    match code_point
+        .filter(|&code_point| is_valid_code_point(code_point))
+        .and_then(char::from_u32)
+    {
+        None => {
+            state.warnings.push(crate::Warning {
+                end: end_position,
+                message: crate::WarningMessage::InvalidCharacterReference,
+                start: start_position,
+            });
+            state.scan_position = end_position;
+        }
+        Some(character) => {
+            state.flush(start_position);
+            state.flushed_position = end_position;
+            state.scan_position = end_position;
+            state.nodes.push(crate::Node::CharacterEntity {
+                character,
+                end: end_position,
+                start: start_position,
+            });
+        }
+    }
+}
+
+/// Code points accepted by Mediawiki in numeric character references.
+fn is_valid_code_point(code_point: u32) -> bool {
+    matches!(
+        code_point,
+        0x09 | 0x0a | 0x0d | 0x20..=0xd7ff | 0xe000..=0xfffd | 0x10000..=0x10ffff
+    )
+}
@@ -95,11 +95,17 @@ fn parse_end_tag(
                 let open_node = state.stack.pop().unwrap();
                 state.flushed_position = tag_end_position;
                 state.scan_position = state.flushed_position;
+                let attributes = match open_node.type_ {
+                    crate::OpenNodeType::Tag { attributes, .. } => attributes,
+                    _ => unreachable!(),
+                };
                 let nodes = std::mem::replace(&mut state.nodes, open_node.nodes);
                 state.nodes.push(crate::Node::Tag {
+                    attributes,
                     end: state.scan_position,
                     name: tag_name,
                     nodes,
+                    raw_content: None,
                     start: open_node.start,
                 });
             }
@@ -2,36 +2,120 @@
 // This is free software distributed under the terms specified in
 // the file LICENSE at the top-level directory of this distribution.
 
+/// Tag names of HTML tags that are allowed in wiki text.
+pub const HTML_TAG_NAMES: &[&str] = &[
+    "abbr",
+    "b",
+    "bdi",
+    "bdo",
+    "big",
+    "blockquote",
+    "br",
+    "caption",
+    "center",
+    "cite",
+    "code",
+    "data",
+    "dd",
+    "del",
+    "dfn",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "font",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "rb",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "small",
+    "span",
+    "strike",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "td",
+    "th",
+    "time",
+    "tr",
+    "tt",
+    "u",
+    "ul",
+    "var",
+    "wbr",
+];
+
 /// Site specific configuration of a wiki.
 ///
 /// This is generated using the program [`fetch_mediawiki_configuration`](https://github.com/portstrom/fetch_mediawiki_configuration).
 pub struct ConfigurationSource<'a> {
+    /// Whether the first letter of page names is converted to uppercase, which is the case on most wikis.
+    pub capital_links: bool,
+
     /// Aliases of the category namespace.
     pub category_namespaces: &'a [&'a str],
 
-    /// Tag names of extension tags.
-    pub extension_tags: &'a [&'a str],
+    /// Tag names of extension tags and how their content is parsed.
+    pub extension_tags: &'a [(&'a str, crate::TagContentModel)],
 
     /// Aliases of the file namespace.
     pub file_namespaces: &'a [&'a str],
 
+    /// Aliases of image options and the type of option they represent. For options that take a value, `$1` stands for the value, such as in `alt=$1` and `$1px`.
+    pub image_options: &'a [(&'a str, crate::ImageOptionType)],
+
+    /// Interwiki prefixes, which refer to pages on other wikis, whether the prefix is the language code of an interlanguage link, and the URL of pages on the other wiki, if known, where `$1` stands for the page name, such as `("fr", true, Some("https://fr.wikipedia.org/wiki/$1"))`.
+    pub interwiki: &'a [(&'a str, bool, Option<&'a str>)],
+
+    /// Characters that can appear in link prefixes, which are attached to the start of a link in some languages. Empty for most wikis.
+    pub link_prefix: &'a str,
+
     /// Characters that can appear in link trails.
     pub link_trail: &'a str,
 
-    /// Magic words that can appear between `__` and `__`.
-    pub magic_words: &'a [&'a str],
+    /// Types of magic links that are recognized, such as `ISBN 978-3-16-148410-0`. Mediawiki doesn't recognize any of them by default.
+    pub magic_links: &'a [crate::MagicLinkType],
+
+    /// Aliases of magic words that can appear between `__` and `__`, and the canonical name of the magic word they represent, such as `("KEINVERZEICHNIS", "NOTOC")`.
+    pub magic_words: &'a [(&'a str, &'a str)],
+
+    /// Names and aliases of namespaces and the number of the namespace they refer to, such as `("Image", 6)`. The first name of each namespace is the name used in titles. The main namespace has no name.
+    pub namespaces: &'a [(&'a str, i32)],
 
     /// Protocols that can be used for external links.
     pub protocols: &'a [&'a str],
 
     /// Magic words that can be used for redirects.
     pub redirect_magic_words: &'a [&'a str],
+
+    /// Aliases of magic variables and the ID of the variable they represent, such as `("PAGENAME", "pagename")`. Aliases are case sensitive.
+    pub variables: &'a [(&'a str, &'a str)],
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Namespace {
     Category,
     File,
+    LanguageLink,
 }
 
 impl crate::Configuration {
@@ -39,27 +123,77 @@ impl crate::Configuration {
     #[must_use]
     pub fn new(source: &ConfigurationSource) -> Self {
         let mut configuration = crate::Configuration {
+            capital_links: source.capital_links,
             character_entities: crate::Trie::new(),
+            image_options: vec![],
+            interwiki: crate::HashMap::new(),
+            link_prefix_character_set: crate::HashSet::new(),
             link_trail_character_set: crate::HashSet::new(),
+            magic_links: source.magic_links.to_vec(),
+            magic_word_ids: vec![],
             magic_words: crate::Trie::new(),
+            namespace_ids: crate::HashMap::new(),
+            namespace_names: crate::HashMap::new(),
             namespaces: crate::Trie::new(),
             protocols: crate::Trie::new(),
             redirect_magic_words: crate::Trie::new(),
             tag_name_map: crate::HashMap::new(),
+            variables: crate::HashMap::new(),
         };
         for (name, character) in crate::html_entities::HTML_ENTITIES {
             configuration
                 .character_entities
                 .add_case_sensitive_term(&format!("{};", name), *character);
         }
+        for (alias, type_) in source.image_options {
+            let (prefix, suffix) = match alias.split_once("$1") {
+                None => (alias.to_string(), None),
+                Some((prefix, suffix)) => (prefix.to_string(), Some(suffix.to_string())),
+            };
+            configuration.image_options.push((prefix, suffix, *type_));
+        }
+        for (prefix, is_language_link, url) in source.interwiki {
+            configuration.interwiki.insert(
+                prefix.to_lowercase(),
+                (*is_language_link, url.map(str::to_string)),
+            );
+            if *is_language_link {
+                configuration
+                    .namespaces
+                    .add_term(&format!("{}:", prefix), Namespace::LanguageLink);
+            }
+        }
+        for (name, id) in source.namespaces {
+            configuration
+                .namespace_ids
+                .insert(crate::title::fold_name(name), *id);
+            configuration
+                .namespace_names
+                .entry(*id)
+                .or_insert_with(|| name.to_string());
+        }
+        for character in source.link_prefix.chars() {
+            configuration.link_prefix_character_set.insert(character);
+        }
         for character in source.link_trail.chars() {
             configuration.link_trail_character_set.insert(character);
         }
         for protocol in source.protocols {
             configuration.protocols.add_term(protocol, ());
         }
-        for magic_word in source.magic_words {
-            configuration.magic_words.add_term(magic_word, ());
+        for (alias, id) in source.magic_words {
+            let index = match configuration
+                .magic_word_ids
+                .iter()
+                .position(|magic_word_id| magic_word_id == id)
+            {
+                None => {
+                    configuration.magic_word_ids.push(id.to_string());
+                    configuration.magic_word_ids.len() - 1
+                }
+                Some(index) => index,
+            };
+            configuration.magic_words.add_term(alias, index);
         }
         for namespace in source.category_namespaces {
             configuration
@@ -76,94 +210,69 @@ impl crate::Configuration {
                 .redirect_magic_words
                 .add_term(redirect_magic_word, ());
         }
-        for tag_name in source.extension_tags {
+        for tag_name in HTML_TAG_NAMES {
             configuration
                 .tag_name_map
-                .insert(tag_name.to_string(), crate::TagClass::ExtensionTag);
+                .insert(tag_name.to_string(), crate::TagClass::Tag);
+        }
+        for (tag_name, content_model) in source.extension_tags {
+            configuration.tag_name_map.insert(
+                tag_name.to_string(),
+                crate::TagClass::ExtensionTag(*content_model),
+            );
         }
-        for tag_name in [
-            "abbr",
-            "b",
-            "bdi",
-            "bdo",
-            "big",
-            "blockquote",
-            "br",
-            "caption",
-            "center",
-            "cite",
-            "code",
-            "data",
-            "dd",
-            "del",
-            "dfn",
-            "div",
-            "dl",
-            "dt",
-            "em",
-            "font",
-            "h1",
-            "h2",
-            "h3",
-            "h4",
-            "h5",
-            "h6",
-            "hr",
-            "i",
-            "ins",
-            "kbd",
-            "li",
-            "mark",
-            "ol",
-            "p",
-            "pre",
-            "q",
-            "rb",
-            "rp",
-            "rt",
-            "ruby",
-            "s",
-            "samp",
-            "small",
-            "span",
-            "strike",
-            "strong",
-            "sub",
-            "sup",
-            "table",
-            "td",
-            "th",
-            "time",
-            "tr",
-            "tt",
-            "u",
-            "ul",
-            "var",
-            "wbr",
-        ]
-        .iter()
-        {
+        for (alias, id) in source.variables {
             configuration
-                .tag_name_map
-                .insert(tag_name.to_string(), crate::TagClass::Tag);
+                .variables
+                .insert(alias.to_string(), id.to_string());
         }
         configuration
     }
 
+    /// Parses wiki text into structured data with the given options.
+    ///
+    /// ```
+    /// use parse_wiki_text_2::{Configuration, Node, ParseOptions};
+    ///
+    /// let options = ParseOptions {
+    ///     bare_urls: true,
+    ///     ..ParseOptions::default()
+    /// };
+    /// let output = Configuration::default()
+    ///     .parse_with_options("See https://example.org/alpha.", &options)
+    ///     .unwrap();
+    /// assert!(matches!(
+    ///     output.nodes[1],
+    ///     Node::ExternalLink { bracketed: false, end: 29, start: 4, .. }
+    /// ));
+    /// ```
+    pub fn parse_with_options<'a>(
+        &self,
+        wiki_text: &'a str,
+        options: &crate::ParseOptions,
+    ) -> Result<crate::Output<'a>, crate::parse::ParseError<'a>> {
+        crate::parse::parse(self, wiki_text, options)
+    }
     /// Parses wiki text into structured data with a user defined timeout.
     pub fn parse_with_timeout<'a>(
         &self,
         wiki_text: &'a str,
         max_duration: std::time::Duration,
     ) -> Result<crate::Output<'a>, crate::parse::ParseError<'a>> {
-        crate::parse::parse(self, wiki_text, max_duration)
+        self.parse_with_options(
+            wiki_text,
+            &crate::ParseOptions {
+                max_duration,
+                ..crate::ParseOptions::default()
+            },
+        )
     }
     /// Parses wiki text into structured data with a default timeout of 5 seconds.
     pub fn parse<'a>(
         &self,
         wiki_text: &'a str,
     ) -> Result<crate::Output<'a>, crate::parse::ParseError<'a>> {
-        crate::parse::parse(self, wiki_text, std::time::Duration::from_secs(5))
+        self.parse_with_options(wiki_text, &crate::ParseOptions::default())
     }
     /// Parses wiki text into structured data with no time out.
     /// This function may run for extremely long lengths of time on certain articles
@@ -171,7 +280,18 @@ impl crate::Configuration {
         &self,
         wiki_text: &'a str,
     ) -> Result<crate::Output<'a>, crate::parse::ParseError<'a>> {
-        crate::parse::parse(self, wiki_text, std::time::Duration::ZERO)
+        self.parse_with_timeout(wiki_text, std::time::Duration::ZERO)
+    }
+}
+
+impl Default for crate::ParseOptions {
+    /// Returns the options used by [`Configuration::parse`](crate::Configuration::parse): bare URLs are not parsed as links, the wiki text is parsed as a page being viewed, and parsing is aborted after 5 seconds.
+    fn default() -> Self {
+        crate::ParseOptions {
+            bare_urls: false,
+            include_mode: crate::IncludeMode::View,
+            max_duration: std::time::Duration::from_secs(5),
+        }
     }
 }
 
@@ -2,56 +2,176 @@
 // This is free software distributed under the terms specified in
 // the file LICENSE at the top-level directory of this distribution.
 
+use crate::{
+    ImageAlignment, ImageFormat, ImageOptionType, ImageVerticalAlignment, TagContentModel,
+};
+
+/// The canonical names of the magic words that Mediawiki recognizes between `__` and `__`, each of which is also an alias of itself.
+pub const MAGIC_WORDS: &[(&str, &str)] = &[
+    ("DISAMBIG", "DISAMBIG"),
+    ("FORCETOC", "FORCETOC"),
+    ("HIDDENCAT", "HIDDENCAT"),
+    ("INDEX", "INDEX"),
+    ("NEWSECTIONLINK", "NEWSECTIONLINK"),
+    ("NOCC", "NOCC"),
+    ("NOCOLLABORATIONHUBTOC", "NOCOLLABORATIONHUBTOC"),
+    ("NOCONTENTCONVERT", "NOCONTENTCONVERT"),
+    ("NOEDITSECTION", "NOEDITSECTION"),
+    ("NOGALLERY", "NOGALLERY"),
+    ("NOGLOBAL", "NOGLOBAL"),
+    ("NOINDEX", "NOINDEX"),
+    ("NONEWSECTIONLINK", "NONEWSECTIONLINK"),
+    ("NOTC", "NOTC"),
+    ("NOTITLECONVERT", "NOTITLECONVERT"),
+    ("NOTOC", "NOTOC"),
+    ("STATICREDIRECT", "STATICREDIRECT"),
+    ("TOC", "TOC"),
+];
+
 pub fn create_configuration() -> crate::Configuration {
     crate::Configuration::new(&crate::ConfigurationSource {
+        capital_links: true,
         category_namespaces: &["category"],
         extension_tags: &[
-            "categorytree",
-            "ce",
-            "charinsert",
-            "chem",
-            "gallery",
-            "graph",
-            "hiero",
-            "imagemap",
-            "indicator",
-            "inputbox",
-            "mapframe",
-            "maplink",
-            "math",
-            "nowiki",
-            "poem",
-            "pre",
-            "ref",
-            "references",
-            "score",
-            "section",
-            "source",
-            "syntaxhighlight",
-            "templatedata",
-            "timeline",
+            ("categorytree", TagContentModel::Raw),
+            ("ce", TagContentModel::Raw),
+            ("charinsert", TagContentModel::Raw),
+            ("chem", TagContentModel::Raw),
+            ("gallery", TagContentModel::Gallery),
+            ("graph", TagContentModel::Raw),
+            ("hiero", TagContentModel::Raw),
+            ("imagemap", TagContentModel::LineOriented),
+            ("indicator", TagContentModel::Wikitext),
+            ("inputbox", TagContentModel::Raw),
+            ("mapframe", TagContentModel::Raw),
+            ("maplink", TagContentModel::Raw),
+            ("math", TagContentModel::Raw),
+            ("nowiki", TagContentModel::Raw),
+            ("poem", TagContentModel::LineOriented),
+            ("pre", TagContentModel::Raw),
+            ("ref", TagContentModel::Wikitext),
+            ("references", TagContentModel::Wikitext),
+            ("score", TagContentModel::Raw),
+            ("section", TagContentModel::Wikitext),
+            ("source", TagContentModel::Raw),
+            ("syntaxhighlight", TagContentModel::Raw),
+            ("templatedata", TagContentModel::Raw),
+            ("timeline", TagContentModel::Raw),
         ],
         file_namespaces: &["file", "image"],
+        image_options: &[
+            ("$1px", ImageOptionType::Size),
+            ("alt=$1", ImageOptionType::Alt),
+            (
+                "baseline",
+                ImageOptionType::VerticalAlignment(ImageVerticalAlignment::Baseline),
+            ),
+            ("border", ImageOptionType::Border),
+            (
+                "bottom",
+                ImageOptionType::VerticalAlignment(ImageVerticalAlignment::Bottom),
+            ),
+            ("center", ImageOptionType::Alignment(ImageAlignment::Center)),
+            ("centre", ImageOptionType::Alignment(ImageAlignment::Center)),
+            ("class=$1", ImageOptionType::Class),
+            ("enframed", ImageOptionType::Format(ImageFormat::Frame)),
+            ("frame", ImageOptionType::Format(ImageFormat::Frame)),
+            ("framed", ImageOptionType::Format(ImageFormat::Frame)),
+            ("frameless", ImageOptionType::Format(ImageFormat::Frameless)),
+            ("lang=$1", ImageOptionType::Lang),
+            ("left", ImageOptionType::Alignment(ImageAlignment::Left)),
+            ("link=$1", ImageOptionType::Link),
+            (
+                "middle",
+                ImageOptionType::VerticalAlignment(ImageVerticalAlignment::Middle),
+            ),
+            ("none", ImageOptionType::Alignment(ImageAlignment::None)),
+            ("page $1", ImageOptionType::Page),
+            ("page=$1", ImageOptionType::Page),
+            ("right", ImageOptionType::Alignment(ImageAlignment::Right)),
+            (
+                "sub",
+                ImageOptionType::VerticalAlignment(ImageVerticalAlignment::Sub),
+            ),
+            (
+                "sup",
+                ImageOptionType::VerticalAlignment(ImageVerticalAlignment::Super),
+            ),
+            (
+                "super",
+                ImageOptionType::VerticalAlignment(ImageVerticalAlignment::Super),
+            ),
+            (
+                "text-bottom",
+                ImageOptionType::VerticalAlignment(ImageVerticalAlignment::TextBottom),
+            ),
+            (
+                "text-top",
+                ImageOptionType::VerticalAlignment(ImageVerticalAlignment::TextTop),
+            ),
+            ("thumb", ImageOptionType::Format(ImageFormat::Thumbnail)),
+            (
+                "thumb=$1",
+                ImageOptionType::Format(ImageFormat::ManualThumbnail),
+            ),
+            ("thumbnail", ImageOptionType::Format(ImageFormat::Thumbnail)),
+            (
+                "thumbnail=$1",
+                ImageOptionType::Format(ImageFormat::ManualThumbnail),
+            ),
+            (
+                "top",
+                ImageOptionType::VerticalAlignment(ImageVerticalAlignment::Top),
+            ),
+            ("upright", ImageOptionType::Upright),
+            ("upright $1", ImageOptionType::Upright),
+            ("upright=$1", ImageOptionType::Upright),
+        ],
+        interwiki: &[
+            (
+                "commons",
+                false,
+                Some("https://commons.wikimedia.org/wiki/$1"),
+            ),
+            ("de", true, Some("https://de.wikipedia.org/wiki/$1")),
+            ("en", true, Some("https://en.wikipedia.org/wiki/$1")),
+            ("es", true, Some("https://es.wikipedia.org/wiki/$1")),
+            ("fr", true, Some("https://fr.wikipedia.org/wiki/$1")),
+            ("ja", true, Some("https://ja.wikipedia.org/wiki/$1")),
+            ("meta", false, Some("https://meta.wikimedia.org/wiki/$1")),
+            ("w", false, Some("https://en.wikipedia.org/wiki/$1")),
+            ("wikipedia", false, Some("https://en.wikipedia.org/wiki/$1")),
+            ("wikt", false, Some("https://en.wiktionary.org/wiki/$1")),
+            (
+                "wiktionary",
+                false,
+                Some("https://en.wiktionary.org/wiki/$1"),
+            ),
+        ],
+        link_prefix: "",
         link_trail: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
-        magic_words: &[
-            "DISAMBIG",
-            "FORCETOC",
-            "HIDDENCAT",
-            "INDEX",
-            "NEWSECTIONLINK",
-            "NOCC",
-            "NOCOLLABORATIONHUBTOC",
-            "NOCONTENTCONVERT",
-            "NOEDITSECTION",
-            "NOGALLERY",
-            "NOGLOBAL",
-            "NOINDEX",
-            "NONEWSECTIONLINK",
-            "NOTC",
-            "NOTITLECONVERT",
-            "NOTOC",
-            "STATICREDIRECT",
-            "TOC",
+        magic_links: &[],
+        magic_words: MAGIC_WORDS,
+        namespaces: &[
+            ("Media", -2),
+            ("Special", -1),
+            ("Talk", 1),
+            ("User", 2),
+            ("User talk", 3),
+            ("Project", 4),
+            ("Project talk", 5),
+            ("File", 6),
+            ("Image", 6),
+            ("File talk", 7),
+            ("Image talk", 7),
+            ("MediaWiki", 8),
+            ("MediaWiki talk", 9),
+            ("Template", 10),
+            ("Template talk", 11),
+            ("Help", 12),
+            ("Help talk", 13),
+            ("Category", 14),
+            ("Category talk", 15),
         ],
         protocols: &[
             "//",
@@ -84,5 +204,31 @@ pub fn create_configuration() -> crate::Configuration {
             "xmpp:",
         ],
         redirect_magic_words: &["REDIRECT"],
+        variables: &[
+            ("!", "!"),
+            ("BASEPAGENAME", "basepagename"),
+            ("CURRENTDAY", "currentday"),
+            ("CURRENTDAY2", "currentday2"),
+            ("CURRENTDAYNAME", "currentdayname"),
+            ("CURRENTDOW", "currentdow"),
+            ("CURRENTHOUR", "currenthour"),
+            ("CURRENTMONTH", "currentmonth"),
+            ("CURRENTMONTH1", "currentmonth1"),
+            ("CURRENTMONTH2", "currentmonth"),
+            ("CURRENTMONTHABBREV", "currentmonthabbrev"),
+            ("CURRENTMONTHNAME", "currentmonthname"),
+            ("CURRENTTIME", "currenttime"),
+            ("CURRENTTIMESTAMP", "currenttimestamp"),
+            ("CURRENTWEEK", "currentweek"),
+            ("CURRENTYEAR", "currentyear"),
+            ("FULLPAGENAME", "fullpagename"),
+            ("FULLPAGENAMEE", "fullpagenamee"),
+            ("NAMESPACE", "namespace"),
+            ("NAMESPACEE", "namespacee"),
+            ("PAGENAME", "pagename"),
+            ("PAGENAMEE", "pagenamee"),
+            ("ROOTPAGENAME", "rootpagename"),
+            ("SUBPAGENAME", "subpagename"),
+        ],
     })
 }
@@ -0,0 +1,462 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+use crate::{Cow, HashMap, VisitMut};
+
+/// The maximum number of templates expanded within each other, the same as the default in Mediawiki.
+const MAX_DEPTH: usize = 40;
+
+/// Sources of the templates and functions that wiki text is expanded with.
+pub struct ExpansionContext<'c> {
+    /// Parser functions in addition to the built-in ones.
+    pub functions: &'c crate::ParserFunctions,
+
+    /// Source of the values of magic variables.
+    pub page: &'c dyn crate::PageContext,
+
+    /// Source of the wiki text of templates.
+    pub templates: &'c dyn TemplateProvider,
+}
+
+/// Source of the wiki text of templates to be expanded.
+pub trait TemplateProvider {
+    /// Returns the wiki text of the template with the given name, or `None` if there is no such template.
+    ///
+    /// The name is the title of the template as normalized by [`Configuration::template_title`](crate::Configuration::template_title), without the namespace prefix for templates in the template namespace and with the namespace prefix for pages in other namespaces.
+    fn template(&self, name: &str) -> Option<Cow<'_, str>>;
+}
+
+impl<S: std::hash::BuildHasher> TemplateProvider for HashMap<String, String, S> {
+    fn template(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.get(name)
+            .map(|wiki_text| Cow::Borrowed(wiki_text.as_str()))
+    }
+}
+
+struct Expander<'c> {
+    configuration: &'c crate::Configuration,
+    functions: &'c crate::ParserFunctions,
+    max_duration: std::time::Duration,
+
+    /// The names of the templates being expanded, from the outermost to the innermost.
+    names: Vec<String>,
+    page: &'c dyn crate::PageContext,
+    start_time: std::time::Instant,
+    templates: &'c dyn TemplateProvider,
+}
+
+/// Replaces the templates and parameters in a tree of nodes by the wiki text they expand to.
+struct Frame<'b, 'c> {
+    /// The arguments of the template call being expanded, or no arguments when expanding the page itself.
+    arguments: &'b HashMap<String, String>,
+    error: Option<crate::ParseError<'static>>,
+    expander: &'b mut Expander<'c>,
+    wiki_text: &'b str,
+}
+
+impl crate::Configuration {
+    /// Expands the templates in wiki text and parses the result, with a timeout of 5 seconds.
+    ///
+    /// Each template is looked up in the template provider of the context and replaced by its wiki text, with parameters such as `{{{1}}}` and `{{{name|default}}}` substituted by the arguments of the template call. Templates within the expanded wiki text are expanded in turn, up to a depth of 40 templates, and a template that directly or indirectly includes itself is replaced by an error message. Templates that the provider doesn't have are left as they are.
+    ///
+    /// Magic variables are replaced by the values given by the page context of the context, and are left as they are if the page context doesn't know the variable.
+    ///
+    /// Calls of the built-in parser functions and the functions of the context are replaced by their result. Other calls, such as `{{Template:Name}}`, `{{:Name}}` and `{{User:Name}}`, transclude the page named by the function name, a colon and the first parameter, with the other parameters as arguments, like templates.
+    pub fn expand(
+        &self,
+        wiki_text: &str,
+        context: &ExpansionContext,
+    ) -> Result<crate::Expansion, crate::ParseError<'static>> {
+        let mut expander = Expander {
+            configuration: self,
+            functions: context.functions,
+            max_duration: std::time::Duration::from_secs(5),
+            names: vec![],
+            page: context.page,
+            start_time: std::time::Instant::now(),
+            templates: context.templates,
+        };
+        let wiki_text = expander.expand(wiki_text, &HashMap::new())?;
+        let output = expander.parse(&wiki_text)?.into_owned();
+        Ok(crate::Expansion { output, wiki_text })
+    }
+}
+
+impl Expander<'_> {
+    fn expand(
+        &mut self,
+        wiki_text: &str,
+        arguments: &HashMap<String, String>,
+    ) -> Result<String, crate::ParseError<'static>> {
+        let mut output = self.parse(wiki_text)?;
+        let mut frame = Frame {
+            arguments,
+            error: None,
+            expander: self,
+            wiki_text,
+        };
+        frame.visit_nodes(&mut output.nodes);
+        match frame.error {
+            None => Ok(output.to_wikitext(wiki_text)),
+            Some(error) => Err(error),
+        }
+    }
+
+    /// Parses wiki text within the time remaining of the timeout, as a page being viewed at the top level and as a template being transcluded otherwise.
+    fn parse<'a>(
+        &self,
+        wiki_text: &'a str,
+    ) -> Result<crate::Output<'a>, crate::ParseError<'static>> {
+        let remaining_duration = self.max_duration.saturating_sub(self.start_time.elapsed());
+        if remaining_duration.is_zero() {
+            return Err(crate::ParseError::TimedOut {
+                execution_time: self.start_time.elapsed(),
+                output: crate::Output {
+                    nodes: vec![],
+                    warnings: vec![],
+                },
+            });
+        }
+        crate::parse::parse(
+            self.configuration,
+            wiki_text,
+            &crate::ParseOptions {
+                include_mode: if self.names.is_empty() {
+                    crate::IncludeMode::View
+                } else {
+                    crate::IncludeMode::Transclusion
+                },
+                max_duration: remaining_duration,
+                ..crate::ParseOptions::default()
+            },
+        )
+        .map_err(|crate::ParseError::TimedOut { output, .. }| {
+            crate::ParseError::TimedOut {
+                execution_time: self.start_time.elapsed(),
+                output: output.into_owned(),
+            }
+        })
+    }
+}
+
+impl Frame<'_, '_> {
+    /// Collects the arguments of a template call, numbering the arguments without a name.
+    fn arguments(&self, parameters: &[crate::Parameter]) -> HashMap<String, String> {
+        let mut arguments = HashMap::new();
+        let mut index = 0;
+        for parameter in parameters {
+            match &parameter.name {
+                Some(name) => {
+                    let name = self.text(name);
+                    let value = self.text(&parameter.value);
+                    arguments.insert(name.trim().to_string(), value.trim().to_string());
+                }
+                None => {
+                    // Unlike named arguments, arguments without a name keep the whitespace around them.
+                    index += 1;
+                    let start =
+                        crate::state::skip_whitespace_backwards(self.wiki_text, parameter.start);
+                    let end = crate::state::skip_whitespace_forwards(self.wiki_text, parameter.end);
+                    arguments.insert(
+                        index.to_string(),
+                        nodes_text(self.wiki_text, &parameter.value, start, end),
+                    );
+                }
+            }
+        }
+        arguments
+    }
+
+    /// Expands the template with the given name, collecting its arguments only if the template exists.
+    fn expand_template(
+        &mut self,
+        name: &str,
+        arguments: impl FnOnce(&mut Self) -> HashMap<String, String>,
+    ) -> Option<String> {
+        let mut title = self.expander.configuration.template_title(name)?;
+        if title.interwiki.is_some() {
+            return None;
+        }
+        title.fragment = None;
+        let name = if title.namespace == crate::title::TEMPLATE_NAMESPACE {
+            title.page_name.clone()
+        } else {
+            title.to_string()
+        };
+        if self.expander.names.contains(&name) {
+            return Some(format!(
+                "<span class=\"error\">Template loop detected: [[{}]]</span>",
+                title
+            ));
+        }
+        if self.expander.names.len() >= MAX_DEPTH {
+            return Some(format!(
+                "<span class=\"error\">Template recursion depth limit exceeded ({})</span>",
+                MAX_DEPTH
+            ));
+        }
+        let wiki_text = self.expander.templates.template(&name)?.into_owned();
+        let arguments = arguments(self);
+        self.expander.names.push(name);
+        let result = self.expander.expand(&wiki_text, &arguments);
+        self.expander.names.pop();
+        match result {
+            Ok(wiki_text) => Some(wiki_text),
+            Err(error) => {
+                self.error = Some(error);
+                None
+            }
+        }
+    }
+
+    /// Expands templates and parameters in text that isn't parsed, such as URLs and link targets, from its wiki text.
+    fn expand_text(&mut self, text: &mut Cow<'_, str>) {
+        if self.error.is_none() && text.contains("{{") {
+            match self.expander.expand(text, self.arguments) {
+                Ok(expanded) => *text = Cow::Owned(expanded),
+                Err(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    /// Returns the wiki text of nodes that have been expanded.
+    fn text(&self, nodes: &[crate::Node]) -> String {
+        match (nodes.first(), nodes.last()) {
+            (Some(first), Some(last)) => nodes_text(
+                self.wiki_text,
+                nodes,
+                crate::Positioned::start(first),
+                crate::Positioned::end(last),
+            ),
+            _ => String::new(),
+        }
+    }
+}
+
+impl<'a> VisitMut<'a> for Frame<'_, '_> {
+    fn visit_node(&mut self, node: &mut crate::Node<'a>) {
+        if self.error.is_none() {
+            match node {
+                crate::Node::Category { .. } => self.visit_category(node),
+                crate::Node::ExternalLink { .. } => self.visit_external_link(node),
+                crate::Node::Function { .. } => self.visit_function(node),
+                crate::Node::Ignored { .. } => self.visit_ignored(node),
+                crate::Node::Image { .. } => self.visit_image(node),
+                crate::Node::Link { .. } => self.visit_link(node),
+                crate::Node::Parameter { .. } => self.visit_parameter(node),
+                crate::Node::Template { .. } => self.visit_template(node),
+                crate::Node::Variable { .. } => self.visit_variable(node),
+                _ => self.visit_children(node),
+            }
+        }
+    }
+
+    fn visit_attribute(&mut self, attribute: &mut crate::Attribute<'a>) {
+        if let Some(value) = &mut attribute.value {
+            self.expand_text(&mut value.value);
+        }
+    }
+
+    fn visit_category(&mut self, node: &mut crate::Node<'a>) {
+        self.visit_children(node);
+        if let crate::Node::Category { target, .. } = node {
+            self.expand_text(target);
+        }
+    }
+
+    fn visit_external_link(&mut self, node: &mut crate::Node<'a>) {
+        self.visit_children(node);
+        if let crate::Node::ExternalLink { url, .. } = node {
+            self.expand_text(url);
+        }
+    }
+
+    fn visit_function(&mut self, node: &mut crate::Node<'a>) {
+        if let crate::Node::Function {
+            end,
+            name,
+            parameters,
+            start,
+        } = node
+        {
+            self.visit_nodes(name);
+            let name = self.text(name);
+            let name = name.trim();
+            let functions = self.expander.functions;
+            let value = if functions.contains(name) {
+                functions.call(name, std::mem::take(parameters), &mut |mut nodes| {
+                    self.visit_nodes(&mut nodes);
+                    self.text(&nodes)
+                })
+            } else {
+                // Like in Mediawiki, a page called like a parser function, such as `{{Template:Name}}` or `{{:Name}}`, is transcluded.
+                let mut title = format!("{}:", name);
+                if let Some(parameter) = parameters.first_mut() {
+                    self.visit_function_parameter(parameter);
+                    title.push_str(&self.text(&parameter.value));
+                }
+                let value = self.expand_template(&title, |frame| {
+                    let mut parameters: Vec<_> = std::mem::take(parameters)
+                        .into_iter()
+                        .skip(1)
+                        .map(template_parameter)
+                        .collect();
+                    for parameter in &mut parameters {
+                        frame.visit_template_parameter(parameter);
+                    }
+                    frame.arguments(&parameters)
+                });
+                match value {
+                    None => {
+                        for parameter in parameters.iter_mut().skip(1) {
+                            self.visit_function_parameter(parameter);
+                        }
+                        return;
+                    }
+                    Some(value) => value,
+                }
+            };
+            *node = crate::Node::Text {
+                end: *end,
+                start: *start,
+                value: Cow::Owned(value),
+            };
+        }
+    }
+
+    fn visit_image(&mut self, node: &mut crate::Node<'a>) {
+        self.visit_children(node);
+        if let crate::Node::Image { target, .. } = node {
+            self.expand_text(target);
+        }
+    }
+
+    fn visit_ignored(&mut self, node: &mut crate::Node<'a>) {
+        if let crate::Node::Ignored { end, start } = node {
+            *node = crate::Node::Text {
+                end: *end,
+                start: *start,
+                value: Cow::Borrowed(""),
+            };
+        }
+    }
+
+    fn visit_link(&mut self, node: &mut crate::Node<'a>) {
+        // The text of a reparsed link is parsed from the target alone, so it's replaced by the expanded target instead of being visited.
+        if let crate::Node::Link {
+            end,
+            prefix,
+            reparsed: true,
+            start,
+            target,
+            text,
+            trail,
+        } = node
+        {
+            self.expand_text(target);
+            // The text takes the place of the code between `[[` and `]]`.
+            *text = vec![crate::Node::Text {
+                end: trail
+                    .as_ref()
+                    .map_or(*end, |trail| trail.start)
+                    .saturating_sub(2),
+                start: prefix.as_ref().map_or(*start, |prefix| prefix.end) + 2,
+                value: target.clone(),
+            }];
+            return;
+        }
+        self.visit_children(node);
+        if let crate::Node::Link { target, .. } = node {
+            self.expand_text(target);
+        }
+    }
+
+    fn visit_parameter(&mut self, node: &mut crate::Node<'a>) {
+        self.visit_children(node);
+        if let crate::Node::Parameter {
+            default,
+            end,
+            name,
+            start,
+        } = node
+        {
+            let value = match self.arguments.get(self.text(name).trim()) {
+                Some(value) => value.clone(),
+                None => match default {
+                    None => return,
+                    Some(default) => self.text(default),
+                },
+            };
+            *node = crate::Node::Text {
+                end: *end,
+                start: *start,
+                value: Cow::Owned(value),
+            };
+        }
+    }
+
+    fn visit_template(&mut self, node: &mut crate::Node<'a>) {
+        self.visit_children(node);
+        if let crate::Node::Template {
+            end,
+            name,
+            parameters,
+            start,
+        } = node
+        {
+            let name = self.text(name);
+            if let Some(value) = self.expand_template(&name, |frame| frame.arguments(parameters)) {
+                *node = crate::Node::Text {
+                    end: *end,
+                    start: *start,
+                    value: Cow::Owned(value),
+                };
+            }
+        }
+    }
+
+    fn visit_variable(&mut self, node: &mut crate::Node<'a>) {
+        if let crate::Node::Variable { end, id, start, .. } = node
+            && let Some(value) = self.expander.page.variable(id)
+        {
+            *node = crate::Node::Text {
+                end: *end,
+                start: *start,
+                value: Cow::Owned(value),
+            };
+        }
+    }
+}
+
+/// Converts a parameter of a parser function to an argument of a template, named by the text before its first `=`, if any.
+fn template_parameter(parameter: crate::FunctionParameter) -> crate::Parameter {
+    let (name, value) = match crate::parser_functions::split_argument(parameter.value) {
+        Ok((name, value)) => (Some(name), value),
+        Err(value) => (None, value),
+    };
+    crate::Parameter {
+        end: parameter.end,
+        name,
+        start: parameter.start,
+        value,
+    }
+}
+
+/// Returns the wiki text of nodes that may have been modified, together with the unparsed code around them, between the given positions.
+fn nodes_text(wiki_text: &str, nodes: &[crate::Node], start: usize, end: usize) -> String {
+    let mut text = String::new();
+    let mut position = start;
+    for node in nodes {
+        let node_start = crate::Positioned::start(node);
+        if let Some(code) = wiki_text.get(position..node_start) {
+            text.push_str(code);
+        }
+        text.push_str(&node.to_wikitext(wiki_text));
+        position = crate::Positioned::end(node);
+    }
+    if let Some(code) = wiki_text.get(position..end) {
+        text.push_str(code);
+    }
+    text
+}
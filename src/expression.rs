@@ -0,0 +1,450 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+#[derive(Clone, Copy)]
+enum Token {
+    CloseBracket,
+    Number(f64),
+    OpenBracket,
+    Operator(Operator),
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Operator {
+    Abs,
+    Acos,
+    And,
+    Asin,
+    Atan,
+    Ceil,
+    Cos,
+    Divide,
+    Equality,
+    Exp,
+    Exponent,
+    Floor,
+    Fmod,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Ln,
+    Minus,
+    Mod,
+    Not,
+    NotEqual,
+    Or,
+    Pow,
+    Plus,
+    Round,
+    Sin,
+    Sqrt,
+    Tan,
+    Times,
+    Trunc,
+}
+
+struct Parser {
+    position: usize,
+    tokens: Vec<Token>,
+}
+
+/// Evaluates an expression of the parser functions `#expr` and `#ifexpr`, with the same operators, precedence and error messages as the ParserFunctions extension of Mediawiki.
+///
+/// Returns `None` for an empty expression, or the error message if the expression is invalid.
+pub fn evaluate_expression(expression: &str) -> Result<Option<f64>, String> {
+    let tokens = tokenize(&expression.to_lowercase())?;
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+    let mut parser = Parser {
+        position: 0,
+        tokens,
+    };
+    let value = parser.expression(None, 0)?;
+    match parser.tokens.get(parser.position) {
+        None => Ok(Some(value)),
+        Some(Token::CloseBracket) => Err("Expression error: Unexpected closing bracket.".into()),
+        Some(Token::Number(_) | Token::OpenBracket) => {
+            Err("Expression error: Unexpected number.".into())
+        }
+        Some(Token::Operator(operator)) => Err(format!(
+            "Expression error: Unexpected {} operator.",
+            operator.name()
+        )),
+    }
+}
+
+/// Formats a number the way PHP converts a floating point number to a string, with 14 significant digits.
+pub fn format_number(value: f64) -> String {
+    if value.is_nan() {
+        return "NAN".into();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "INF" } else { "-INF" }.into();
+    }
+    if value == 0.0 {
+        return if value.is_sign_negative() { "-0" } else { "0" }.into();
+    }
+    let scientific = format!("{:.13e}", value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+    let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+    if !(-5..15).contains(&exponent) {
+        let mantissa = if mantissa.contains('.') {
+            mantissa.to_string()
+        } else {
+            format!("{}.0", mantissa)
+        };
+        return format!(
+            "{}E{}{}",
+            mantissa,
+            if exponent < 0 { '-' } else { '+' },
+            exponent.abs()
+        );
+    }
+    let decimals = (13 - exponent).max(0) as usize;
+    let fixed = format!("{:.*}", decimals, value);
+    if fixed.contains('.') {
+        fixed
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        fixed
+    }
+}
+
+impl Operator {
+    fn apply(self, left: f64, right: f64) -> Result<f64, String> {
+        Ok(match self {
+            Operator::And => f64::from(left != 0.0 && right != 0.0),
+            Operator::Divide => {
+                if right == 0.0 {
+                    return Err("Division by zero.".into());
+                }
+                left / right
+            }
+            Operator::Equality => f64::from(left == right),
+            Operator::Exponent => left * 10f64.powf(right),
+            Operator::Fmod => {
+                if right == 0.0 {
+                    return Err("Division by zero.".into());
+                }
+                left % right
+            }
+            Operator::Greater => f64::from(left > right),
+            Operator::GreaterEqual => f64::from(left >= right),
+            Operator::Less => f64::from(left < right),
+            Operator::LessEqual => f64::from(left <= right),
+            Operator::Minus => left - right,
+            Operator::Mod => {
+                let right = right as i64;
+                if right == 0 {
+                    return Err("Division by zero.".into());
+                }
+                (left as i64).wrapping_rem(right) as f64
+            }
+            Operator::NotEqual => f64::from(left != right),
+            Operator::Or => f64::from(left != 0.0 || right != 0.0),
+            Operator::Plus => left + right,
+            Operator::Pow => {
+                let value = left.powf(right);
+                if value.is_nan() {
+                    return Err("In ^: result is not a number.".into());
+                }
+                value
+            }
+            Operator::Round => {
+                let factor = 10f64.powi(right as i32);
+                (left * factor).round() / factor
+            }
+            Operator::Times => left * right,
+            _ => unreachable!(),
+        })
+    }
+
+    fn apply_unary(self, value: f64) -> Result<f64, String> {
+        Ok(match self {
+            Operator::Abs => value.abs(),
+            Operator::Acos | Operator::Asin => {
+                if !(-1.0..=1.0).contains(&value) {
+                    return Err(format!(
+                        "Invalid argument for {}: < -1 or > 1.",
+                        self.name()
+                    ));
+                }
+                if self == Operator::Acos {
+                    value.acos()
+                } else {
+                    value.asin()
+                }
+            }
+            Operator::Atan => value.atan(),
+            Operator::Ceil => value.ceil(),
+            Operator::Cos => value.cos(),
+            Operator::Exp => value.exp(),
+            Operator::Floor => value.floor(),
+            Operator::Ln => {
+                if value <= 0.0 {
+                    return Err("Invalid argument for ln: <= 0.".into());
+                }
+                value.ln()
+            }
+            Operator::Minus => -value,
+            Operator::Not => f64::from(value == 0.0),
+            Operator::Plus => value,
+            Operator::Sin => value.sin(),
+            Operator::Sqrt => {
+                if value < 0.0 {
+                    return Err("In sqrt: result is not a number.".into());
+                }
+                value.sqrt()
+            }
+            Operator::Tan => value.tan(),
+            Operator::Trunc => value.trunc(),
+            _ => unreachable!(),
+        })
+    }
+
+    /// The precedence of the operator when used between two operands, if it can be.
+    fn binary_precedence(self) -> Option<u8> {
+        match self {
+            Operator::Exponent => Some(10),
+            Operator::Pow => Some(8),
+            Operator::Divide | Operator::Fmod | Operator::Mod | Operator::Times => Some(7),
+            Operator::Minus | Operator::Plus => Some(6),
+            Operator::Round => Some(5),
+            Operator::Equality
+            | Operator::Greater
+            | Operator::GreaterEqual
+            | Operator::Less
+            | Operator::LessEqual
+            | Operator::NotEqual => Some(4),
+            Operator::And => Some(3),
+            Operator::Or => Some(2),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Operator::Abs => "abs",
+            Operator::Acos => "acos",
+            Operator::And => "and",
+            Operator::Asin => "asin",
+            Operator::Atan => "atan",
+            Operator::Ceil => "ceil",
+            Operator::Cos => "cos",
+            Operator::Divide => "/",
+            Operator::Equality => "=",
+            Operator::Exp => "exp",
+            Operator::Exponent => "e",
+            Operator::Floor => "floor",
+            Operator::Fmod => "fmod",
+            Operator::Greater => ">",
+            Operator::GreaterEqual => ">=",
+            Operator::Less => "<",
+            Operator::LessEqual => "<=",
+            Operator::Ln => "ln",
+            Operator::Minus => "-",
+            Operator::Mod => "mod",
+            Operator::Not => "not",
+            Operator::NotEqual => "<>",
+            Operator::Or => "or",
+            Operator::Plus => "+",
+            Operator::Pow => "^",
+            Operator::Round => "round",
+            Operator::Sin => "sin",
+            Operator::Sqrt => "sqrt",
+            Operator::Tan => "tan",
+            Operator::Times => "*",
+            Operator::Trunc => "trunc",
+        }
+    }
+
+    /// The precedence of the operator when used before a single operand, if it can be.
+    fn unary_precedence(self) -> Option<u8> {
+        match self {
+            Operator::Minus | Operator::Plus => Some(10),
+            Operator::Abs
+            | Operator::Acos
+            | Operator::Asin
+            | Operator::Atan
+            | Operator::Ceil
+            | Operator::Cos
+            | Operator::Exp
+            | Operator::Floor
+            | Operator::Ln
+            | Operator::Not
+            | Operator::Sin
+            | Operator::Sqrt
+            | Operator::Tan
+            | Operator::Trunc => Some(9),
+            _ => None,
+        }
+    }
+}
+
+impl Parser {
+    /// Evaluates an operand and the operators of at least the given precedence that follow it, from left to right. The operand follows the given operator, if any.
+    fn expression(
+        &mut self,
+        previous_operator: Option<Operator>,
+        min_precedence: u8,
+    ) -> Result<f64, String> {
+        let mut value = self.operand(previous_operator)?;
+        while let Some(Token::Operator(operator)) = self.tokens.get(self.position).copied() {
+            match operator.binary_precedence() {
+                Some(precedence) if precedence >= min_precedence => {
+                    self.position += 1;
+                    let right = self.expression(Some(operator), precedence + 1)?;
+                    value = operator.apply(value, right)?;
+                }
+                _ => break,
+            }
+        }
+        Ok(value)
+    }
+
+    /// Evaluates an operand, which may be preceded by unary operators.
+    fn operand(&mut self, previous_operator: Option<Operator>) -> Result<f64, String> {
+        let Some(token) = self.tokens.get(self.position).copied() else {
+            return Err(match previous_operator {
+                None => "Expression error: Unclosed bracket.".into(),
+                Some(operator) => {
+                    format!("Expression error: Missing operand for {}.", operator.name())
+                }
+            });
+        };
+        self.position += 1;
+        match token {
+            Token::CloseBracket => Err(match previous_operator {
+                None => "Expression error: Unexpected closing bracket.".into(),
+                Some(operator) => {
+                    format!("Expression error: Missing operand for {}.", operator.name())
+                }
+            }),
+            Token::Number(value) => Ok(value),
+            Token::OpenBracket => {
+                let value = self.expression(None, 0)?;
+                match self.tokens.get(self.position) {
+                    Some(Token::CloseBracket) => {
+                        self.position += 1;
+                        Ok(value)
+                    }
+                    _ => Err("Expression error: Unclosed bracket.".into()),
+                }
+            }
+            Token::Operator(Operator::Exponent) => Ok(std::f64::consts::E),
+            Token::Operator(operator) => match operator.unary_precedence() {
+                None => Err(format!(
+                    "Expression error: Unexpected {} operator.",
+                    operator.name()
+                )),
+                Some(precedence) => {
+                    let value = self.expression(Some(operator), precedence + 1)?;
+                    operator.apply_unary(value)
+                }
+            },
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut characters = expression.char_indices().peekable();
+    while let Some((position, character)) = characters.next() {
+        let token = match character {
+            ' ' | '\t' | '\n' | '\r' => continue,
+            '0'..='9' | '.' => {
+                let mut end = position + 1;
+                while let Some((_, '0'..='9' | '.')) = characters.peek() {
+                    characters.next();
+                    end += 1;
+                }
+                Token::Number(parse_number(&expression[position..end]))
+            }
+            'a'..='z' => {
+                let mut end = position + 1;
+                while let Some((_, 'a'..='z')) = characters.peek() {
+                    characters.next();
+                    end += 1;
+                }
+                let word = &expression[position..end];
+                Token::Operator(match word {
+                    "abs" => Operator::Abs,
+                    "acos" => Operator::Acos,
+                    "and" => Operator::And,
+                    "asin" => Operator::Asin,
+                    "atan" => Operator::Atan,
+                    "ceil" => Operator::Ceil,
+                    "cos" => Operator::Cos,
+                    "div" => Operator::Divide,
+                    "e" => Operator::Exponent,
+                    "exp" => Operator::Exp,
+                    "floor" => Operator::Floor,
+                    "fmod" => Operator::Fmod,
+                    "ln" => Operator::Ln,
+                    "mod" => Operator::Mod,
+                    "not" => Operator::Not,
+                    "or" => Operator::Or,
+                    "pi" => {
+                        tokens.push(Token::Number(std::f64::consts::PI));
+                        continue;
+                    }
+                    "round" => Operator::Round,
+                    "sin" => Operator::Sin,
+                    "sqrt" => Operator::Sqrt,
+                    "tan" => Operator::Tan,
+                    "trunc" => Operator::Trunc,
+                    _ => return Err(format!("Expression error: Unrecognized word \"{}\".", word)),
+                })
+            }
+            '(' => Token::OpenBracket,
+            ')' => Token::CloseBracket,
+            '*' => Token::Operator(Operator::Times),
+            '+' => Token::Operator(Operator::Plus),
+            '-' | '−' => Token::Operator(Operator::Minus),
+            '/' => Token::Operator(Operator::Divide),
+            '=' => Token::Operator(Operator::Equality),
+            '^' => Token::Operator(Operator::Pow),
+            '!' if characters.next_if(|(_, next)| *next == '=').is_some() => {
+                Token::Operator(Operator::NotEqual)
+            }
+            '<' => Token::Operator(if characters.next_if(|(_, next)| *next == '=').is_some() {
+                Operator::LessEqual
+            } else if characters.next_if(|(_, next)| *next == '>').is_some() {
+                Operator::NotEqual
+            } else {
+                Operator::Less
+            }),
+            '>' => Token::Operator(if characters.next_if(|(_, next)| *next == '=').is_some() {
+                Operator::GreaterEqual
+            } else {
+                Operator::Greater
+            }),
+            _ => {
+                return Err(format!(
+                    "Expression error: Unrecognized punctuation character \"{}\".",
+                    character
+                ));
+            }
+        };
+        if let (Some(Token::Number(_)), Token::Number(_)) = (tokens.last(), token) {
+            return Err("Expression error: Unexpected number.".into());
+        }
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Parses a number the way PHP converts a string to a floating point number, ignoring anything after the first invalid character.
+fn parse_number(text: &str) -> f64 {
+    let end = match text.match_indices('.').nth(1) {
+        None => text.len(),
+        Some((position, _)) => position,
+    };
+    text[..end].parse().unwrap_or(0.0)
+}
@@ -6,16 +6,19 @@ pub fn parse_external_link_end<'a>(
     state: &mut crate::State<'a>,
     start_position: usize,
     nodes: Vec<crate::Node<'a>>,
+    url: &'a str,
 ) {
     let scan_position = state.scan_position;
     state.flush(scan_position);
     state.scan_position += 1;
     state.flushed_position = state.scan_position;
-    let nodes = std::mem::replace(&mut state.nodes, nodes);
+    let label = std::mem::replace(&mut state.nodes, nodes);
     state.nodes.push(crate::Node::ExternalLink {
+        bracketed: true,
         end: state.scan_position,
-        nodes,
+        label,
         start: start_position,
+        url: crate::Cow::Borrowed(url),
     });
 }
 
@@ -30,17 +33,177 @@ pub fn parse_external_link_end_of_line(state: &mut crate::State) {
     state.rewind(open_node.nodes, open_node.start);
 }
 
+/// Parses a URL standing on its own in the text, which starts at a letter, as an external link, the way Mediawiki links such URLs.
+pub fn parse_bare_url(state: &mut crate::State, configuration: &crate::Configuration) {
+    let start_position = state.scan_position;
+    state.scan_position += 1;
+    if state.wiki_text[..start_position]
+        .chars()
+        .next_back()
+        .is_some_and(|character| character.is_alphanumeric() || character == '_')
+        || state.stack.iter().any(|open_node| {
+            matches!(
+                open_node.type_,
+                crate::OpenNodeType::ExternalLink { .. }
+                    | crate::OpenNodeType::Link {
+                        namespace: None,
+                        ..
+                    }
+            )
+        })
+    {
+        return;
+    }
+    let Ok((protocol_length, _)) = configuration
+        .protocols
+        .find(&state.wiki_text[start_position..])
+    else {
+        return;
+    };
+    let protocol_end_position = start_position + protocol_length;
+    let mut end_position = parse_url_end(state, protocol_end_position, false);
+    // Like in Mediawiki, the escaped angle brackets `&lt;` and `&gt;` end the URL.
+    if let Some(offset) = ["&lt;", "&gt;"]
+        .iter()
+        .filter_map(|entity| state.wiki_text[start_position..end_position].find(entity))
+        .min()
+    {
+        end_position = start_position + offset;
+    }
+    // The `=` signs that end a heading at the end of the line are not part of the URL.
+    if let Some(level) = state
+        .stack
+        .iter()
+        .find_map(|open_node| match open_node.type_ {
+            crate::OpenNodeType::Heading { level } => Some(level),
+            _ => None,
+        })
+        && state.wiki_text[end_position..]
+            .split('\n')
+            .next()
+            .is_some_and(|rest| rest.trim_matches([' ', '\t']).is_empty())
+    {
+        let url = &state.wiki_text[start_position..end_position];
+        end_position -= (url.len() - url.trim_end_matches('=').len()).min(level as usize);
+    }
+    end_position -= trailing_punctuation_length(&state.wiki_text[start_position..end_position]);
+    if end_position <= protocol_end_position {
+        return;
+    }
+    state.flush(start_position);
+    state.nodes.push(crate::Node::ExternalLink {
+        bracketed: false,
+        end: end_position,
+        label: vec![],
+        start: start_position,
+        url: crate::Cow::Borrowed(&state.wiki_text[start_position..end_position]),
+    });
+    state.flushed_position = end_position;
+    state.scan_position = end_position;
+}
+
 pub fn parse_external_link_start(state: &mut crate::State, configuration: &crate::Configuration) {
-    let scheme_start_position = state.scan_position + 1;
+    let url_start_position = state.scan_position + 1;
     match configuration
         .protocols
-        .find(&state.wiki_text[scheme_start_position..])
+        .find(&state.wiki_text[url_start_position..])
     {
         Err(_) => {
-            state.scan_position = scheme_start_position;
+            state.scan_position = url_start_position;
         }
-        Ok(_) => {
-            state.push_open_node(crate::OpenNodeType::ExternalLink, scheme_start_position);
+        Ok((protocol_length, _)) => {
+            let url_end_position = parse_url_end(state, url_start_position + protocol_length, true);
+            let label_start_position = url_end_position
+                + state.wiki_text[url_end_position..]
+                    .chars()
+                    .take_while(|character| *character != '\n' && character.is_whitespace())
+                    .map(char::len_utf8)
+                    .sum::<usize>();
+            state.push_open_node(
+                crate::OpenNodeType::ExternalLink {
+                    url: &state.wiki_text[url_start_position..url_end_position],
+                },
+                label_start_position,
+            );
+        }
+    }
+}
+
+/// Returns the position where a URL ends, given the position after its protocol. The URL ends at whitespace or at a character that can't appear in URLs. Templates and parameters in the URL of a bracketed link are kept whole, while a bare URL ends where they start.
+fn parse_url_end(state: &crate::State, position: usize, is_bracketed: bool) -> usize {
+    let mut end_position = position;
+    let mut characters = state.wiki_text[position..].chars();
+    while let Some(character) = characters.next() {
+        let rest = &state.wiki_text[end_position..];
+        if is_bracketed
+            && rest.starts_with("{{")
+            && let Some(length) = template_length(rest)
+        {
+            end_position += length;
+            characters = state.wiki_text[end_position..].chars();
+            continue;
+        }
+        if character.is_control()
+            || character.is_whitespace()
+            || matches!(character, '"' | '<' | '>' | '[' | ']' | '\u{fffd}')
+            || rest.starts_with("''")
+            || rest.starts_with("{{")
+            || rest.starts_with("}}")
+            || character == '|' && !state.stack.is_empty()
+        {
+            break;
+        }
+        end_position += character.len_utf8();
+    }
+    end_position
+}
+
+/// Returns the length of the template or parameter that the text starts with, if it ends before the end of the line.
+fn template_length(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut position = 0;
+    while let Some(rest) = text.get(position..) {
+        if rest.starts_with("{{") {
+            depth += 1;
+            position += 2;
+        } else if rest.starts_with("}}") {
+            depth -= 1;
+            position += 2;
+            if depth == 0 {
+                return Some(position);
+            }
+        } else {
+            let character = rest.chars().next()?;
+            if character == '\n' {
+                return None;
+            }
+            position += character.len_utf8();
+        }
+    }
+    None
+}
+
+/// Returns the length of the punctuation at the end of a bare URL that is not part of the URL, the way Mediawiki determines it. A closing parenthesis is punctuation only if the URL has no opening parenthesis, and a semicolon ending a character entity is not punctuation.
+fn trailing_punctuation_length(url: &str) -> usize {
+    let has_parenthesis = url.contains('(');
+    let mut length = url
+        .bytes()
+        .rev()
+        .take_while(|character| {
+            matches!(character, b'!' | b',' | b'.' | b':' | b';' | b'?')
+                || *character == b')' && !has_parenthesis
+        })
+        .count();
+    if length > 0 && url.as_bytes()[url.len() - length] == b';' {
+        let entity_end_position = url.len() - length + 1;
+        if let Some(entity_start_position) = url[..entity_end_position].rfind('&')
+            && crate::character_entity::decode_character_entity(
+                &url[entity_start_position..entity_end_position],
+            )
+            .is_some()
+        {
+            length -= 1;
         }
     }
+    length
 }
@@ -2,9 +2,9 @@
 pub(crate) fn parse_function(state: &mut crate::state::State) {
     match state.stack.pop() {
         Some(crate::OpenNode {
+                 nodes,
                  type_: crate::OpenNodeType::Template { name, .. },
                  start,
-                 ..
              }) => {
             assert!(name.is_none());
 
@@ -29,7 +29,7 @@ pub(crate) fn parse_function(state: &mut crate::state::State) {
             }];
 
             state.stack.push(crate::OpenNode {
-                nodes: vec![],
+                nodes,
                 start,
                 type_: crate::OpenNodeType::Function { name, parameters },
             });
@@ -0,0 +1,122 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+use crate::image::{push_text, slice};
+
+/// Parses the content of a gallery tag, which ends at the given content end position, into an entry for each line that has a target or options.
+pub fn parse_gallery<'a>(
+    wiki_text: &'a str,
+    configuration: &crate::Configuration,
+    nodes: Vec<crate::Node<'a>>,
+    content_end_position: usize,
+) -> Vec<crate::GalleryEntry<'a>> {
+    let mut entries = vec![];
+    let mut line_start_position = match nodes.first() {
+        None => content_end_position,
+        Some(node) => crate::Positioned::start(node),
+    };
+    let mut line_nodes = vec![];
+    for node in nodes {
+        match node {
+            crate::Node::Text { end, start, value } => {
+                let mut piece_start_position = start;
+                for (index, _) in value.match_indices('\n') {
+                    let line_end_position = start + index;
+                    push_text(
+                        &mut line_nodes,
+                        piece_start_position,
+                        line_end_position,
+                        slice(&value, piece_start_position - start, index),
+                    );
+                    entries.extend(parse_entry(
+                        wiki_text,
+                        configuration,
+                        std::mem::take(&mut line_nodes),
+                        line_start_position,
+                        line_end_position,
+                    ));
+                    piece_start_position = line_end_position + 1;
+                    line_start_position = piece_start_position;
+                }
+                push_text(
+                    &mut line_nodes,
+                    piece_start_position,
+                    end,
+                    slice(&value, piece_start_position - start, value.len()),
+                );
+            }
+            node => line_nodes.push(node),
+        }
+    }
+    entries.extend(parse_entry(
+        wiki_text,
+        configuration,
+        line_nodes,
+        line_start_position,
+        content_end_position,
+    ));
+    entries
+}
+
+/// Parses a line of a gallery into an entry, unless the line has neither a target nor options, such as when it's blank or only has comments. The target is the code before the first `|` with comments left out, and the options are the content after it.
+fn parse_entry<'a>(
+    wiki_text: &'a str,
+    configuration: &crate::Configuration,
+    nodes: Vec<crate::Node<'a>>,
+    line_start_position: usize,
+    line_end_position: usize,
+) -> Option<crate::GalleryEntry<'a>> {
+    let mut option_nodes = None;
+    let mut target = String::new();
+    let mut nodes = nodes.into_iter();
+    for node in nodes.by_ref() {
+        match &node {
+            crate::Node::Comment { .. } => {}
+            crate::Node::Text { end, start, value } => {
+                if let Some(index) = value.find('|') {
+                    target.push_str(&value[..index]);
+                    let mut nodes = vec![];
+                    push_text(
+                        &mut nodes,
+                        start + index + 1,
+                        *end,
+                        slice(value, index + 1, value.len()),
+                    );
+                    option_nodes = Some(nodes);
+                    break;
+                }
+                target.push_str(value);
+            }
+            _ => target.push_str(
+                &wiki_text[crate::Positioned::start(&node)..crate::Positioned::end(&node)],
+            ),
+        }
+    }
+    let target = target.trim();
+    if target.is_empty() && option_nodes.is_none() {
+        return None;
+    }
+    let line = &wiki_text[line_start_position..line_end_position];
+    let start = line_start_position + line.len() - line.trim_start().len();
+    let title = configuration.file_title(target);
+    let target = if wiki_text[start..].starts_with(target) {
+        crate::Cow::Borrowed(&wiki_text[start..start + target.len()])
+    } else {
+        crate::Cow::Owned(target.to_string())
+    };
+    let options = match option_nodes {
+        None => vec![],
+        Some(mut option_nodes) => {
+            option_nodes.extend(nodes);
+            crate::image::parse_image_options(configuration, option_nodes, line_end_position)
+        }
+    };
+    Some(crate::GalleryEntry {
+        end: line_start_position + line.trim_end().len(),
+        options,
+        start,
+        target,
+        title,
+    })
+}
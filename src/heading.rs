@@ -8,7 +8,15 @@ pub fn parse_heading_end(state: &mut crate::State) {
         end_position -= 1;
     }
     let open_node = state.stack.pop().unwrap();
-    if state.get_byte(end_position - 1) != Some(b'=') || end_position < open_node.start + 3 {
+    // The closing `=` must not be part of an unterminated comment or of code left out by the include mode.
+    if state.get_byte(end_position - 1) != Some(b'=')
+        || end_position < open_node.start + 3
+        || matches!(
+            state.nodes.last(),
+            Some(crate::Node::Comment { end, .. } | crate::Node::Ignored { end, .. })
+                if *end >= end_position
+        )
+    {
         state.warnings.push(crate::Warning {
             end: end_position,
             message: crate::WarningMessage::InvalidHeadingSyntaxRewinding,
@@ -43,7 +51,7 @@ pub fn parse_heading_end(state: &mut crate::State) {
             }
             Some(crate::Node::Text { end, start, value }) => {
                 *start = inner_start_position;
-                *value = &state.wiki_text[inner_start_position..*end];
+                *value = crate::Cow::Borrowed(&state.wiki_text[inner_start_position..*end]);
                 false
             }
             Some(_) => true,
@@ -54,7 +62,7 @@ pub fn parse_heading_end(state: &mut crate::State) {
                 crate::Node::Text {
                     end,
                     start: inner_start_position,
-                    value: &state.wiki_text[inner_start_position..end],
+                    value: crate::Cow::Borrowed(&state.wiki_text[inner_start_position..end]),
                 },
             );
         }
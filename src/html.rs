@@ -0,0 +1,388 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+/// Names of HTML attributes that are written for start tags. Other attributes, such as event handlers and styles, are left out.
+const ATTRIBUTE_NAMES: &[&str] = &[
+    "abbr", "align", "cite", "class", "colspan", "datetime", "dir", "headers", "id", "lang",
+    "rowspan", "scope", "title", "valign",
+];
+
+/// Names of HTML elements that have no end tag.
+const VOID_TAG_NAMES: &[&str] = &["br", "hr", "wbr"];
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Format {
+    Bold,
+    Italic,
+}
+
+struct Writer<'b> {
+    /// The number of external links without a label written so far, used to number them.
+    external_link_count: usize,
+
+    /// The formatting toggled on, in the order the elements were opened.
+    formats: Vec<Format>,
+    is_paragraph_open: bool,
+    output: String,
+    resolve_link: &'b dyn Fn(&str) -> String,
+}
+
+impl crate::Output<'_> {
+    /// Renders the nodes as HTML similar to the HTML produced by Mediawiki, such as for an offline preview of a page.
+    ///
+    /// The given function is called with the target of each link and returns the URL the link refers to.
+    ///
+    /// Headings, paragraphs, bold and italic text, lists, preformatted text, tables, HTML tags, links, external links and magic links are rendered. HTML tags are written only with a restricted set of attributes. Templates, functions, parameters, comments, categories, images, magic words and redirects are left out, since rendering them requires information not available in the wiki text. Extension tags are also left out, except `nowiki` and `pre`, which are rendered as text.
+    ///
+    /// ```
+    /// use parse_wiki_text_2::Configuration;
+    ///
+    /// let output = Configuration::default().parse("==a==\n'''b''' [[c d|e]]").unwrap();
+    /// assert_eq!(
+    ///     output.to_html(|target| format!("/wiki/{}", target.replace(' ', "_"))),
+    ///     "<h2>a</h2><p><b>b</b> <a href=\"/wiki/c_d\">e</a></p>"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_html(&self, resolve_link: impl Fn(&str) -> String) -> String {
+        let mut writer = Writer {
+            external_link_count: 0,
+            formats: vec![],
+            is_paragraph_open: false,
+            output: String::new(),
+            resolve_link: &resolve_link,
+        };
+        for node in &self.nodes {
+            if is_block(node) {
+                writer.close_paragraph();
+                writer.node(node);
+            } else if let crate::Node::ParagraphBreak { .. } = node {
+                writer.close_paragraph();
+            } else {
+                let position = writer.output.len();
+                writer.node(node);
+                if !writer.is_paragraph_open && !writer.output[position..].trim_start().is_empty() {
+                    let content = writer.output.split_off(position);
+                    writer.output.push_str("<p>");
+                    writer.output.push_str(content.trim_start());
+                    writer.is_paragraph_open = true;
+                }
+            }
+        }
+        writer.close_paragraph();
+        writer.output
+    }
+}
+
+impl Writer<'_> {
+    /// Writes the value of an HTML attribute, keeping character entities as they are written in the wiki text.
+    fn attribute_value(&mut self, value: &str) {
+        for (position, character) in value.char_indices() {
+            if character == '&'
+                && value[position..]
+                    .split_inclusive(';')
+                    .next()
+                    .is_some_and(|entity| {
+                        crate::character_entity::decode_character_entity(entity).is_some()
+                    })
+            {
+                self.output.push('&');
+            } else {
+                self.text(character.encode_utf8(&mut [0; 4]));
+            }
+        }
+    }
+
+    fn close_formats(&mut self) {
+        while let Some(format) = self.formats.pop() {
+            self.output.push_str(format.end_tag());
+        }
+    }
+
+    fn close_paragraph(&mut self) {
+        self.close_formats();
+        if self.is_paragraph_open {
+            self.output.truncate(self.output.trim_end().len());
+            self.output.push_str("</p>");
+            self.is_paragraph_open = false;
+        }
+    }
+
+    /// Writes nodes within an element, with formatting that starts and ends within the element.
+    fn element(&mut self, name: &str, nodes: &[crate::Node]) {
+        let formats = std::mem::take(&mut self.formats);
+        self.output.push('<');
+        self.output.push_str(name);
+        self.output.push('>');
+        self.nodes(nodes);
+        self.close_formats();
+        self.output.push_str("</");
+        self.output.push_str(name);
+        self.output.push('>');
+        self.formats = formats;
+    }
+
+    fn node(&mut self, node: &crate::Node) {
+        match node {
+            crate::Node::Bold { .. } => self.toggle(&[Format::Bold]),
+            crate::Node::BoldItalic { .. } => self.toggle(&[Format::Italic, Format::Bold]),
+            crate::Node::Category { .. }
+            | crate::Node::Comment { .. }
+            | crate::Node::Function { .. }
+            | crate::Node::Gallery { .. }
+            | crate::Node::Ignored { .. }
+            | crate::Node::Image { .. }
+            | crate::Node::LanguageLink { .. }
+            | crate::Node::MagicWord { .. }
+            | crate::Node::Parameter { .. }
+            | crate::Node::Redirect { .. }
+            | crate::Node::Template { .. }
+            | crate::Node::Variable { .. } => {}
+            crate::Node::CharacterEntity { character, .. } => {
+                self.text(character.encode_utf8(&mut [0; 4]))
+            }
+            crate::Node::DefinitionList { items, .. } => {
+                self.output.push_str("<dl>");
+                for item in items {
+                    self.element(
+                        match item.type_ {
+                            crate::DefinitionListItemType::Details => "dd",
+                            crate::DefinitionListItemType::Term => "dt",
+                        },
+                        &item.nodes,
+                    );
+                }
+                self.output.push_str("</dl>");
+            }
+            crate::Node::EndTag { name, .. } => {
+                if crate::configuration::HTML_TAG_NAMES.contains(&name.as_ref())
+                    && !VOID_TAG_NAMES.contains(&name.as_ref())
+                {
+                    self.output.push_str("</");
+                    self.output.push_str(name);
+                    self.output.push('>');
+                }
+            }
+            crate::Node::ExternalLink {
+                bracketed,
+                label,
+                url,
+                ..
+            } => self.external_link(*bracketed, url, label),
+            crate::Node::Heading { level, nodes, .. } => {
+                self.element(&format!("h{}", level), nodes);
+            }
+            crate::Node::HorizontalDivider { .. } => self.output.push_str("<hr>"),
+            crate::Node::Italic { .. } => self.toggle(&[Format::Italic]),
+            crate::Node::Link {
+                prefix,
+                target,
+                text,
+                trail,
+                ..
+            } => {
+                self.output.push_str("<a href=\"");
+                self.text(&(self.resolve_link)(target));
+                self.output.push_str("\">");
+                if let Some(prefix) = prefix {
+                    self.text(&prefix.value);
+                }
+                if text.is_empty() {
+                    self.text(crate::link::pipe_trick(target));
+                } else {
+                    self.nodes(text);
+                }
+                if let Some(trail) = trail {
+                    self.text(&trail.value);
+                }
+                self.output.push_str("</a>");
+            }
+            crate::Node::MagicLink {
+                id, raw_id, type_, ..
+            } => {
+                self.output.push_str("<a class=\"");
+                self.output.push_str(match type_ {
+                    crate::MagicLinkType::Isbn => "internal mw-magiclink-isbn",
+                    crate::MagicLinkType::Pmid => "external mw-magiclink-pmid",
+                    crate::MagicLinkType::Rfc => "external mw-magiclink-rfc",
+                });
+                self.output.push_str("\" href=\"");
+                match type_ {
+                    crate::MagicLinkType::Isbn => {
+                        self.text(&(self.resolve_link)(&format!("Special:BookSources/{}", id)))
+                    }
+                    _ => self.text(&type_.url(id).unwrap_or_default()),
+                }
+                self.output.push_str("\">");
+                self.text(type_.keyword());
+                self.output.push(' ');
+                self.text(raw_id);
+                self.output.push_str("</a>");
+            }
+            crate::Node::OrderedList { items, .. } => self.list("ol", items),
+            crate::Node::ParagraphBreak { .. } => {}
+            crate::Node::Preformatted { nodes, .. } => self.element("pre", nodes),
+            crate::Node::StartTag {
+                attributes, name, ..
+            } => {
+                if crate::configuration::HTML_TAG_NAMES.contains(&name.as_ref()) {
+                    self.output.push('<');
+                    self.output.push_str(name);
+                    for attribute in attributes {
+                        if !ATTRIBUTE_NAMES.contains(&attribute.name.as_ref()) {
+                            continue;
+                        }
+                        self.output.push(' ');
+                        self.output.push_str(&attribute.name);
+                        self.output.push_str("=\"");
+                        if let Some(value) = &attribute.value {
+                            self.attribute_value(&value.value);
+                        }
+                        self.output.push('"');
+                    }
+                    self.output.push('>');
+                }
+            }
+            crate::Node::Table { captions, rows, .. } => {
+                self.output.push_str("<table>");
+                for caption in captions {
+                    self.element("caption", &caption.content);
+                }
+                for row in rows {
+                    self.output.push_str("<tr>");
+                    for cell in &row.cells {
+                        self.element(
+                            match cell.type_ {
+                                crate::TableCellType::Heading => "th",
+                                crate::TableCellType::Ordinary => "td",
+                            },
+                            &cell.content,
+                        );
+                    }
+                    self.output.push_str("</tr>");
+                }
+                self.output.push_str("</table>");
+            }
+            crate::Node::Tag {
+                name, raw_content, ..
+            } => match (name.as_ref(), raw_content) {
+                ("nowiki", Some(raw_content)) => self.text(&raw_content.value),
+                ("pre", Some(raw_content)) => {
+                    self.output.push_str("<pre>");
+                    self.text(&raw_content.value);
+                    self.output.push_str("</pre>");
+                }
+                _ => {}
+            },
+            crate::Node::Text { value, .. } => self.text(value),
+            crate::Node::UnorderedList { items, .. } => self.list("ul", items),
+        }
+    }
+
+    fn nodes(&mut self, nodes: &[crate::Node]) {
+        for node in nodes {
+            self.node(node);
+        }
+    }
+
+    fn external_link(&mut self, bracketed: bool, url: &str, label: &[crate::Node]) {
+        self.output.push_str("<a class=\"external\" href=\"");
+        self.text(url);
+        self.output.push_str("\">");
+        if !bracketed {
+            self.text(url);
+        } else if label.is_empty() {
+            self.external_link_count += 1;
+            self.output
+                .push_str(&format!("[{}]", self.external_link_count));
+        } else {
+            let formats = std::mem::take(&mut self.formats);
+            self.nodes(label);
+            self.close_formats();
+            self.formats = formats;
+        }
+        self.output.push_str("</a>");
+    }
+
+    fn list(&mut self, name: &str, items: &[crate::ListItem]) {
+        self.output.push('<');
+        self.output.push_str(name);
+        self.output.push('>');
+        for item in items {
+            self.element("li", &item.nodes);
+        }
+        self.output.push_str("</");
+        self.output.push_str(name);
+        self.output.push('>');
+    }
+
+    /// Writes text with the characters that have special meaning in HTML escaped.
+    fn text(&mut self, text: &str) {
+        for character in text.chars() {
+            match character {
+                '"' => self.output.push_str("&quot;"),
+                '&' => self.output.push_str("&amp;"),
+                '<' => self.output.push_str("&lt;"),
+                '>' => self.output.push_str("&gt;"),
+                _ => self.output.push(character),
+            }
+        }
+    }
+
+    /// Toggles formatting, closing and reopening elements as needed to keep them nested.
+    fn toggle(&mut self, formats: &[Format]) {
+        let opened: Vec<_> = formats
+            .iter()
+            .filter(|format| !self.formats.contains(format))
+            .copied()
+            .collect();
+        let index = self
+            .formats
+            .iter()
+            .position(|format| formats.contains(format))
+            .unwrap_or(self.formats.len());
+        let mut reopened = vec![];
+        while self.formats.len() > index {
+            let format = self.formats.pop().unwrap();
+            self.output.push_str(format.end_tag());
+            if !formats.contains(&format) {
+                reopened.insert(0, format);
+            }
+        }
+        for format in reopened.into_iter().chain(opened) {
+            self.output.push_str(format.start_tag());
+            self.formats.push(format);
+        }
+    }
+}
+
+impl Format {
+    fn end_tag(self) -> &'static str {
+        match self {
+            Format::Bold => "</b>",
+            Format::Italic => "</i>",
+        }
+    }
+
+    fn start_tag(self) -> &'static str {
+        match self {
+            Format::Bold => "<b>",
+            Format::Italic => "<i>",
+        }
+    }
+}
+
+/// Checks whether a node is rendered as an element that can't be part of a paragraph.
+fn is_block(node: &crate::Node) -> bool {
+    matches!(
+        node,
+        crate::Node::DefinitionList { .. }
+            | crate::Node::Heading { .. }
+            | crate::Node::HorizontalDivider { .. }
+            | crate::Node::OrderedList { .. }
+            | crate::Node::Preformatted { .. }
+            | crate::Node::Table { .. }
+            | crate::Node::UnorderedList { .. }
+    )
+}
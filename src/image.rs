@@ -0,0 +1,175 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+/// Splits the content of an image link at each `|` and classifies each part as an image option.
+pub fn parse_image_options<'a>(
+    configuration: &crate::Configuration,
+    nodes: Vec<crate::Node<'a>>,
+    end_position: usize,
+) -> Vec<crate::ImageOption<'a>> {
+    let mut options = vec![];
+    let mut option_start_position = match nodes.first() {
+        None => end_position,
+        Some(node) => crate::Positioned::start(node),
+    };
+    let mut option_nodes = vec![];
+    for node in nodes {
+        match node {
+            crate::Node::Text { end, start, value } => {
+                let mut piece_start_position = start;
+                for (index, _) in value.match_indices('|') {
+                    let separator_position = start + index;
+                    push_text(
+                        &mut option_nodes,
+                        piece_start_position,
+                        separator_position,
+                        slice(&value, piece_start_position - start, index),
+                    );
+                    options.push(parse_image_option(
+                        configuration,
+                        option_start_position,
+                        separator_position,
+                        std::mem::take(&mut option_nodes),
+                    ));
+                    piece_start_position = separator_position + 1;
+                    option_start_position = piece_start_position;
+                }
+                push_text(
+                    &mut option_nodes,
+                    piece_start_position,
+                    end,
+                    slice(&value, piece_start_position - start, value.len()),
+                );
+            }
+            node => option_nodes.push(node),
+        }
+    }
+    options.push(parse_image_option(
+        configuration,
+        option_start_position,
+        end_position,
+        option_nodes,
+    ));
+    if let Some(option) = options
+        .iter_mut()
+        .rev()
+        .find(|option| option.type_ == crate::ImageOptionType::Unrecognized)
+    {
+        option.type_ = crate::ImageOptionType::Caption;
+    }
+    options
+}
+
+fn parse_image_option<'a>(
+    configuration: &crate::Configuration,
+    mut start_position: usize,
+    mut end_position: usize,
+    mut nodes: Vec<crate::Node<'a>>,
+) -> crate::ImageOption<'a> {
+    if let Some(crate::Node::Text { start, value, .. }) = nodes.first_mut() {
+        let trimmed_length = value.trim_start().len();
+        *start += value.len() - trimmed_length;
+        *value = slice(value, value.len() - trimmed_length, value.len());
+        if value.is_empty() {
+            nodes.remove(0);
+        }
+    }
+    if let Some(crate::Node::Text { end, value, .. }) = nodes.last_mut() {
+        let trimmed_length = value.trim_end().len();
+        *end -= value.len() - trimmed_length;
+        *value = slice(value, 0, trimmed_length);
+        if value.is_empty() {
+            nodes.pop();
+        }
+    }
+    if let (Some(first), Some(last)) = (nodes.first(), nodes.last()) {
+        start_position = crate::Positioned::start(first);
+        end_position = crate::Positioned::end(last);
+    }
+    for (prefix, suffix, type_) in &configuration.image_options {
+        if let Some((value_start, value_end)) =
+            match_image_option(&nodes, prefix, suffix.as_deref(), *type_)
+        {
+            if suffix.is_none() {
+                nodes.clear();
+            } else if let crate::Node::Text { end, start, value } = &mut nodes[0] {
+                *end = *start + value_end;
+                *start += value_start;
+                *value = slice(value, value_start, value_end);
+                if value.is_empty() {
+                    nodes.remove(0);
+                }
+            }
+            return crate::ImageOption {
+                end: end_position,
+                start: start_position,
+                type_: *type_,
+                value: nodes,
+            };
+        }
+    }
+    crate::ImageOption {
+        end: end_position,
+        start: start_position,
+        type_: crate::ImageOptionType::Unrecognized,
+        value: nodes,
+    }
+}
+
+/// Matches the nodes of an option against an alias of an image option, returning the byte range of the value within the first node.
+fn match_image_option(
+    nodes: &[crate::Node],
+    prefix: &str,
+    suffix: Option<&str>,
+    type_: crate::ImageOptionType,
+) -> Option<(usize, usize)> {
+    let value = match nodes.first() {
+        Some(crate::Node::Text { value, .. }) => value,
+        _ => return None,
+    };
+    match suffix {
+        None => (nodes.len() == 1 && value == prefix).then_some((0, 0)),
+        Some("") => value
+            .starts_with(prefix)
+            .then_some((prefix.len(), value.len())),
+        Some(suffix) => {
+            if nodes.len() != 1 {
+                return None;
+            }
+            let option_value = value.strip_prefix(prefix)?.strip_suffix(suffix)?.trim_end();
+            if type_ == crate::ImageOptionType::Size && !is_valid_size(option_value) {
+                return None;
+            }
+            Some((prefix.len(), prefix.len() + option_value.len()))
+        }
+    }
+}
+
+/// Checks that a size is a width, a height preceded by `x`, or both, as accepted by Mediawiki.
+fn is_valid_size(value: &str) -> bool {
+    let (width, height) = value.split_once('x').unwrap_or((value, ""));
+    !value.is_empty()
+        && value != "x"
+        && width.bytes().all(|character| character.is_ascii_digit())
+        && height.bytes().all(|character| character.is_ascii_digit())
+}
+
+pub fn push_text<'a>(
+    nodes: &mut Vec<crate::Node<'a>>,
+    start: usize,
+    end: usize,
+    value: crate::Cow<'a, str>,
+) {
+    if !value.is_empty() {
+        nodes.push(crate::Node::Text { end, start, value });
+    }
+}
+
+/// Takes the given byte range of a text, borrowing from the wiki text if the text does.
+pub fn slice<'a>(value: &crate::Cow<'a, str>, start: usize, end: usize) -> crate::Cow<'a, str> {
+    match value {
+        crate::Cow::Borrowed(value) => crate::Cow::Borrowed(&value[start..end]),
+        crate::Cow::Owned(value) => crate::Cow::Owned(value[start..end].to_string()),
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+/// Names of the tags that determine what is included when a page is transcluded.
+const TAG_NAMES: &[&str] = &["includeonly", "noinclude", "onlyinclude"];
+
+/// The code that starts the content that is included when a page that has it is transcluded. Like in Mediawiki, it's only recognized exactly as written.
+const ONLY_INCLUDE_START: &str = "<onlyinclude>";
+
+/// Start tag or end tag of one of the tags that determine what is included.
+struct Tag {
+    /// The byte position in the wiki text after the tag.
+    end: usize,
+    is_end_tag: bool,
+    is_self_closing: bool,
+    name: &'static str,
+}
+
+/// Parses the tag at the scan position if it's one of the tags that determine what is included, leaving out the tag, and in the given include mode, the content the tag leaves out. Returns whether such a tag was parsed.
+pub fn parse_inclusion_tag(state: &mut crate::State, include_mode: crate::IncludeMode) -> bool {
+    let start_position = state.scan_position;
+    let Some(tag) = parse_tag(state.wiki_text, start_position) else {
+        return false;
+    };
+    let end_position = match (include_mode, tag.name, tag.is_end_tag) {
+        (crate::IncludeMode::View, "includeonly", false)
+        | (crate::IncludeMode::Transclusion, "noinclude", false)
+            if !tag.is_self_closing =>
+        {
+            element_end(state.wiki_text, tag.end, tag.name)
+        }
+        (crate::IncludeMode::Transclusion, "onlyinclude", true)
+            if state.wiki_text.contains(ONLY_INCLUDE_START) =>
+        {
+            only_include_end(state.wiki_text, tag.end)
+        }
+        _ => tag.end,
+    };
+    ignore(state, start_position, end_position);
+    true
+}
+
+/// Leaves out the wiki text before the first `<onlyinclude>`, if the wiki text has any.
+pub fn parse_only_include_start(state: &mut crate::State) {
+    if state.wiki_text.contains(ONLY_INCLUDE_START) {
+        let end_position = only_include_end(state.wiki_text, 0);
+        ignore(state, 0, end_position);
+    }
+}
+
+/// Returns the position after the end tag of the element with the given name whose content starts at the given position, or the end of the wiki text if the element has no end tag.
+fn element_end(wiki_text: &str, position: usize, name: &str) -> usize {
+    let mut position = position;
+    while let Some(offset) = wiki_text[position..].find("</") {
+        let tag_start_position = position + offset;
+        if let Some(tag) = parse_tag(wiki_text, tag_start_position)
+            && tag.is_end_tag
+            && tag.name == name
+        {
+            return tag.end;
+        }
+        position = tag_start_position + 2;
+    }
+    wiki_text.len()
+}
+
+fn ignore(state: &mut crate::State, start_position: usize, end_position: usize) {
+    state.flush(start_position);
+    state.nodes.push(crate::Node::Ignored {
+        end: end_position,
+        start: start_position,
+    });
+    state.flushed_position = end_position;
+    state.scan_position = end_position;
+}
+
+/// Returns the position after the next `<onlyinclude>` from the given position, or the end of the wiki text if there is none.
+fn only_include_end(wiki_text: &str, position: usize) -> usize {
+    match wiki_text[position..].find(ONLY_INCLUDE_START) {
+        None => wiki_text.len(),
+        Some(offset) => position + offset + ONLY_INCLUDE_START.len(),
+    }
+}
+
+/// Parses the tag starting at the given position if it's one of the tags that determine what is included. Tag names are recognized without regard to case, and anything between the tag name and `>` is allowed.
+fn parse_tag(wiki_text: &str, position: usize) -> Option<Tag> {
+    let rest = wiki_text[position..].strip_prefix('<')?;
+    let (is_end_tag, rest) = match rest.strip_prefix('/') {
+        None => (false, rest),
+        Some(rest) => (true, rest),
+    };
+    let name_length = rest
+        .bytes()
+        .take(12)
+        .position(|byte| byte.is_ascii_whitespace() || matches!(byte, b'/' | b'>'))?;
+    let name = *TAG_NAMES
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(&rest[..name_length]))?;
+    let tag_length = name_length + rest[name_length..].find('>')?;
+    Some(Tag {
+        end: wiki_text.len() - rest.len() + tag_length + 1,
+        is_end_tag,
+        is_self_closing: !is_end_tag && rest[..tag_length].ends_with('/'),
+        name,
+    })
+}
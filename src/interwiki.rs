@@ -0,0 +1,90 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+use crate::Visit;
+
+impl crate::Configuration {
+    /// Returns the URL of a title on another wiki, or `None` if the title has no interwiki prefix or the URL of the other wiki isn't known.
+    ///
+    /// ```
+    /// use parse_wiki_text_2::Configuration;
+    ///
+    /// let configuration = Configuration::default();
+    /// let title = configuration.title("fr:Tour Eiffel#Histoire").unwrap();
+    /// assert_eq!(
+    ///     configuration.interwiki_url(&title).unwrap(),
+    ///     "https://fr.wikipedia.org/wiki/Tour_Eiffel#Histoire"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn interwiki_url(&self, title: &crate::Title) -> Option<String> {
+        let (_, url) = self.interwiki.get(title.interwiki.as_ref()?)?;
+        let mut url = url
+            .as_ref()?
+            .replace("$1", &crate::page_context::url_encode(&title.page_name));
+        if let Some(fragment) = &title.fragment {
+            url.push('#');
+            url.push_str(&crate::page_context::url_encode(fragment));
+        }
+        Some(url)
+    }
+
+    /// Checks whether an interwiki prefix is the language code of an interlanguage link. Prefixes are recognized without regard to case.
+    #[must_use]
+    pub fn is_language_link_prefix(&self, prefix: &str) -> bool {
+        self.interwiki
+            .get(&prefix.to_lowercase())
+            .is_some_and(|(is_language_link, _)| *is_language_link)
+    }
+}
+
+impl crate::Output<'_> {
+    /// Returns the titles of the interlanguage links of the page, in the order they appear, anywhere in the nodes.
+    ///
+    /// As in Mediawiki, only the first link to each language is included.
+    ///
+    /// ```
+    /// use parse_wiki_text_2::Configuration;
+    ///
+    /// let configuration = Configuration::default();
+    /// let output = configuration
+    ///     .parse("[[fr:Paris]] [[de:Paris]] [[fr:Lutèce]] [[:es:Paris]]")
+    ///     .unwrap();
+    /// let links: Vec<_> = output
+    ///     .language_links(&configuration)
+    ///     .iter()
+    ///     .map(ToString::to_string)
+    ///     .collect();
+    /// assert_eq!(links, ["fr:Paris", "de:Paris"]);
+    /// ```
+    #[must_use]
+    pub fn language_links(&self, configuration: &crate::Configuration) -> Vec<crate::Title> {
+        let mut language_links = LanguageLinks {
+            configuration,
+            titles: vec![],
+        };
+        language_links.visit_nodes(&self.nodes);
+        language_links.titles
+    }
+}
+
+/// Collects the titles of interlanguage links.
+struct LanguageLinks<'c> {
+    configuration: &'c crate::Configuration,
+    titles: Vec<crate::Title>,
+}
+
+impl<'a> Visit<'a> for LanguageLinks<'_> {
+    fn visit_language_link(&mut self, node: &crate::Node<'a>) {
+        if let crate::Node::LanguageLink { target, .. } = node
+            && let Some(title) = self.configuration.title(target)
+            && !self
+                .titles
+                .iter()
+                .any(|existing| existing.interwiki == title.interwiki)
+        {
+            self.titles.push(title);
+        }
+    }
+}
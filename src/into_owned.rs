@@ -0,0 +1,518 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+impl crate::Attribute<'_> {
+    /// Converts the attribute to an attribute that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::Attribute<'static> {
+        crate::Attribute {
+            end: self.end,
+            name: owned(self.name),
+            start: self.start,
+            value: self.value.map(crate::AttributeValue::into_owned),
+        }
+    }
+}
+
+impl crate::AttributeValue<'_> {
+    /// Converts the value to a value that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::AttributeValue<'static> {
+        crate::AttributeValue {
+            end: self.end,
+            quote: self.quote,
+            start: self.start,
+            value: owned(self.value),
+        }
+    }
+}
+
+impl crate::DefinitionListItem<'_> {
+    /// Converts the list item to a list item that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::DefinitionListItem<'static> {
+        crate::DefinitionListItem {
+            end: self.end,
+            nodes: owned_nodes(self.nodes),
+            start: self.start,
+            type_: self.type_,
+        }
+    }
+}
+
+impl crate::FunctionParameter<'_> {
+    /// Converts the parameter to a parameter that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::FunctionParameter<'static> {
+        crate::FunctionParameter {
+            end: self.end,
+            start: self.start,
+            value: owned_nodes(self.value),
+        }
+    }
+}
+
+impl crate::GalleryEntry<'_> {
+    /// Converts the entry to an entry that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::GalleryEntry<'static> {
+        crate::GalleryEntry {
+            end: self.end,
+            options: self
+                .options
+                .into_iter()
+                .map(crate::ImageOption::into_owned)
+                .collect(),
+            start: self.start,
+            target: owned(self.target),
+            title: self.title,
+        }
+    }
+}
+
+impl crate::ImageOption<'_> {
+    /// Converts the option to an option that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::ImageOption<'static> {
+        crate::ImageOption {
+            end: self.end,
+            start: self.start,
+            type_: self.type_,
+            value: owned_nodes(self.value),
+        }
+    }
+}
+
+impl crate::LinkAffix<'_> {
+    /// Converts the link prefix or link trail to one that owns its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::LinkAffix<'static> {
+        crate::LinkAffix {
+            end: self.end,
+            start: self.start,
+            value: owned(self.value),
+        }
+    }
+}
+
+impl crate::RawContent<'_> {
+    /// Converts the raw content to raw content that owns its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::RawContent<'static> {
+        crate::RawContent {
+            end: self.end,
+            start: self.start,
+            value: owned(self.value),
+        }
+    }
+}
+
+impl crate::ListItem<'_> {
+    /// Converts the list item to a list item that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::ListItem<'static> {
+        crate::ListItem {
+            end: self.end,
+            nodes: owned_nodes(self.nodes),
+            start: self.start,
+        }
+    }
+}
+
+impl crate::Node<'_> {
+    /// Converts the node to a node that owns all its text and doesn't borrow from the wiki text.
+    ///
+    /// The converted node has the same shape and positions as the original node, so it can be kept after the wiki text is dropped, sent to another thread or stored in a cache. Positions still refer to the wiki text the node was parsed from.
+    #[must_use]
+    pub fn into_owned(self) -> crate::Node<'static> {
+        match self {
+            crate::Node::Bold { end, start } => crate::Node::Bold { end, start },
+            crate::Node::BoldItalic { end, start } => crate::Node::BoldItalic { end, start },
+            crate::Node::Category {
+                end,
+                ordinal,
+                start,
+                target,
+            } => crate::Node::Category {
+                end,
+                ordinal: owned_nodes(ordinal),
+                start,
+                target: owned(target),
+            },
+            crate::Node::CharacterEntity {
+                character,
+                end,
+                start,
+            } => crate::Node::CharacterEntity {
+                character,
+                end,
+                start,
+            },
+            crate::Node::Comment { end, start } => crate::Node::Comment { end, start },
+            crate::Node::Ignored { end, start } => crate::Node::Ignored { end, start },
+            crate::Node::DefinitionList { end, items, start } => crate::Node::DefinitionList {
+                end,
+                items: items
+                    .into_iter()
+                    .map(crate::DefinitionListItem::into_owned)
+                    .collect(),
+                start,
+            },
+            crate::Node::EndTag { end, name, start } => crate::Node::EndTag {
+                end,
+                name: owned(name),
+                start,
+            },
+            crate::Node::ExternalLink {
+                bracketed,
+                end,
+                label,
+                start,
+                url,
+            } => crate::Node::ExternalLink {
+                bracketed,
+                end,
+                label: owned_nodes(label),
+                start,
+                url: owned(url),
+            },
+            crate::Node::Function {
+                end,
+                name,
+                parameters,
+                start,
+            } => crate::Node::Function {
+                end,
+                name: owned_nodes(name),
+                parameters: parameters
+                    .into_iter()
+                    .map(crate::FunctionParameter::into_owned)
+                    .collect(),
+                start,
+            },
+            crate::Node::Gallery {
+                attributes,
+                end,
+                entries,
+                name,
+                start,
+            } => crate::Node::Gallery {
+                attributes: owned_attributes(attributes),
+                end,
+                entries: entries
+                    .into_iter()
+                    .map(crate::GalleryEntry::into_owned)
+                    .collect(),
+                name: owned(name),
+                start,
+            },
+            crate::Node::Heading {
+                end,
+                level,
+                nodes: content,
+                start,
+            } => crate::Node::Heading {
+                end,
+                level,
+                nodes: owned_nodes(content),
+                start,
+            },
+            crate::Node::HorizontalDivider { end, start } => {
+                crate::Node::HorizontalDivider { end, start }
+            }
+            crate::Node::Image {
+                end,
+                options,
+                start,
+                target,
+            } => crate::Node::Image {
+                end,
+                options: options
+                    .into_iter()
+                    .map(crate::ImageOption::into_owned)
+                    .collect(),
+                start,
+                target: owned(target),
+            },
+            crate::Node::Italic { end, start } => crate::Node::Italic { end, start },
+            crate::Node::LanguageLink {
+                end,
+                start,
+                target,
+                text,
+            } => crate::Node::LanguageLink {
+                end,
+                start,
+                target: owned(target),
+                text: owned_nodes(text),
+            },
+            crate::Node::Link {
+                end,
+                prefix,
+                start,
+                target,
+                text,
+                trail,
+                reparsed,
+            } => crate::Node::Link {
+                end,
+                prefix: prefix.map(crate::LinkAffix::into_owned),
+                start,
+                target: owned(target),
+                text: owned_nodes(text),
+                trail: trail.map(crate::LinkAffix::into_owned),
+                reparsed,
+            },
+            crate::Node::MagicLink {
+                end,
+                id,
+                raw_id,
+                start,
+                type_,
+            } => crate::Node::MagicLink {
+                end,
+                id: owned(id),
+                raw_id: owned(raw_id),
+                start,
+                type_,
+            },
+            crate::Node::MagicWord { end, id, start } => crate::Node::MagicWord {
+                end,
+                id: owned(id),
+                start,
+            },
+            crate::Node::OrderedList { end, items, start } => crate::Node::OrderedList {
+                end,
+                items: items.into_iter().map(crate::ListItem::into_owned).collect(),
+                start,
+            },
+            crate::Node::ParagraphBreak { end, start } => {
+                crate::Node::ParagraphBreak { end, start }
+            }
+            crate::Node::Parameter {
+                default,
+                end,
+                name,
+                start,
+            } => crate::Node::Parameter {
+                default: default.map(owned_nodes),
+                end,
+                name: owned_nodes(name),
+                start,
+            },
+            crate::Node::Preformatted {
+                end,
+                nodes: content,
+                start,
+            } => crate::Node::Preformatted {
+                end,
+                nodes: owned_nodes(content),
+                start,
+            },
+            crate::Node::Redirect { end, target, start } => crate::Node::Redirect {
+                end,
+                target: owned(target),
+                start,
+            },
+            crate::Node::StartTag {
+                attributes,
+                end,
+                name,
+                start,
+            } => crate::Node::StartTag {
+                attributes: owned_attributes(attributes),
+                end,
+                name: owned(name),
+                start,
+            },
+            crate::Node::Table {
+                attributes,
+                captions,
+                end,
+                rows,
+                start,
+            } => crate::Node::Table {
+                attributes: owned_table_attributes(attributes),
+                captions: captions
+                    .into_iter()
+                    .map(crate::TableCaption::into_owned)
+                    .collect(),
+                end,
+                rows: rows.into_iter().map(crate::TableRow::into_owned).collect(),
+                start,
+            },
+            crate::Node::Tag {
+                attributes,
+                end,
+                name,
+                nodes: content,
+                raw_content,
+                start,
+            } => crate::Node::Tag {
+                attributes: owned_attributes(attributes),
+                end,
+                name: owned(name),
+                nodes: owned_nodes(content),
+                raw_content: raw_content.map(crate::RawContent::into_owned),
+                start,
+            },
+            crate::Node::Template {
+                end,
+                name,
+                parameters,
+                start,
+            } => crate::Node::Template {
+                end,
+                name: owned_nodes(name),
+                parameters: parameters
+                    .into_iter()
+                    .map(crate::Parameter::into_owned)
+                    .collect(),
+                start,
+            },
+            crate::Node::Text { end, start, value } => crate::Node::Text {
+                end,
+                start,
+                value: owned(value),
+            },
+            crate::Node::UnorderedList { end, items, start } => crate::Node::UnorderedList {
+                end,
+                items: items.into_iter().map(crate::ListItem::into_owned).collect(),
+                start,
+            },
+            crate::Node::Variable {
+                end,
+                id,
+                name,
+                start,
+            } => crate::Node::Variable {
+                end,
+                id: owned(id),
+                name: owned(name),
+                start,
+            },
+        }
+    }
+}
+
+impl crate::Output<'_> {
+    /// Converts the output to an output that owns all its text and doesn't borrow from the wiki text.
+    ///
+    /// See [`Node::into_owned`](crate::Node::into_owned).
+    #[must_use]
+    pub fn into_owned(self) -> crate::Output<'static> {
+        crate::Output {
+            nodes: owned_nodes(self.nodes),
+            warnings: self.warnings,
+        }
+    }
+}
+
+impl crate::Parameter<'_> {
+    /// Converts the parameter to a parameter that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::Parameter<'static> {
+        crate::Parameter {
+            end: self.end,
+            name: self.name.map(owned_nodes),
+            start: self.start,
+            value: owned_nodes(self.value),
+        }
+    }
+}
+
+impl crate::TableAttribute<'_> {
+    /// Converts the attribute to an attribute that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::TableAttribute<'static> {
+        crate::TableAttribute {
+            end: self.end,
+            name: owned(self.name),
+            name_nodes: owned_nodes(self.name_nodes),
+            start: self.start,
+            value: self.value.map(crate::TableAttributeValue::into_owned),
+        }
+    }
+}
+
+impl crate::TableAttributeValue<'_> {
+    /// Converts the value to a value that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::TableAttributeValue<'static> {
+        crate::TableAttributeValue {
+            end: self.end,
+            nodes: owned_nodes(self.nodes),
+            quote: self.quote,
+            start: self.start,
+        }
+    }
+}
+
+impl crate::TableCaption<'_> {
+    /// Converts the caption to a caption that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::TableCaption<'static> {
+        crate::TableCaption {
+            attributes: self.attributes.map(owned_table_attributes),
+            content: owned_nodes(self.content),
+            end: self.end,
+            start: self.start,
+        }
+    }
+}
+
+impl crate::TableCell<'_> {
+    /// Converts the cell to a cell that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::TableCell<'static> {
+        crate::TableCell {
+            attributes: self.attributes.map(owned_table_attributes),
+            content: owned_nodes(self.content),
+            end: self.end,
+            start: self.start,
+            type_: self.type_,
+        }
+    }
+}
+
+impl crate::TableRow<'_> {
+    /// Converts the row to a row that owns all its text and doesn't borrow from the wiki text.
+    #[must_use]
+    pub fn into_owned(self) -> crate::TableRow<'static> {
+        crate::TableRow {
+            attributes: owned_table_attributes(self.attributes),
+            cells: self
+                .cells
+                .into_iter()
+                .map(crate::TableCell::into_owned)
+                .collect(),
+            end: self.end,
+            start: self.start,
+        }
+    }
+}
+
+fn owned_attributes(attributes: Vec<crate::Attribute>) -> Vec<crate::Attribute<'static>> {
+    attributes
+        .into_iter()
+        .map(crate::Attribute::into_owned)
+        .collect()
+}
+
+fn owned_nodes(nodes: Vec<crate::Node>) -> Vec<crate::Node<'static>> {
+    nodes.into_iter().map(crate::Node::into_owned).collect()
+}
+
+fn owned_table_attributes(
+    attributes: Vec<crate::TableAttribute>,
+) -> Vec<crate::TableAttribute<'static>> {
+    attributes
+        .into_iter()
+        .map(crate::TableAttribute::into_owned)
+        .collect()
+}
+
+fn owned(text: crate::Cow<str>) -> crate::Cow<'static, str> {
+    crate::Cow::Owned(text.into_owned())
+}
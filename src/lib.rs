@@ -52,6 +52,7 @@
 //!
 //! - External links are parsed only when the scheme of the URI of the link is in the configured list of valid protocols. When the scheme is not valid, the link is parsed as plain text.
 //! - Categories and images superficially look they same way as links, but are parsed differently. These can only be distinguished by knowing the namespace aliases from the configuration of the wiki.
+//! - Options of images, such as `thumb` and `alt=`, are recognized by the aliases of image options from the configuration of the wiki, which differ from one language to another.
 //! - Text matching the configured set of magic words is parsed as magic words.
 //! - Extension tags have the same syntax as HTML tags, but are parsed differently. The configuration tells which tag names are to be treated as extension tags.
 //!
@@ -107,56 +108,135 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+mod attribute;
 mod bold_italic;
 mod case_folding_simple;
 mod character_entity;
 mod comment;
 mod configuration;
 mod default;
+mod expand;
+mod expression;
 mod external_link;
 mod function;
+mod gallery;
 mod heading;
+mod html;
 mod html_entities;
+mod image;
+mod inclusion;
+mod interwiki;
+mod into_owned;
 mod line;
 mod link;
 mod list;
+mod magic_link;
 mod magic_word;
+mod page_context;
 mod parse;
+mod parser_functions;
+mod plain_text;
 mod positioned;
 mod redirect;
+mod reference;
+mod serialize;
 mod state;
 mod table;
 mod tag;
 mod template;
+mod title;
 mod trie;
+mod visit;
 mod warning;
 
 pub use configuration::ConfigurationSource;
 use configuration::Namespace;
+pub use expand::{ExpansionContext, TemplateProvider};
+pub use page_context::{Page, PageContext};
 pub use parse::ParseError;
+pub use parser_functions::ParserFunctions;
 use state::{OpenNode, OpenNodeType, State};
 use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
 };
 use trie::Trie;
+pub use visit::{Visit, VisitMut};
 pub use warning::{Warning, WarningMessage};
 
+/// HTML attribute of a tag.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Attribute<'a> {
+    /// The byte position in the wiki text where the element ends.
+    pub end: usize,
+
+    /// The name of the attribute, converted to lowercase.
+    pub name: Cow<'a, str>,
+
+    /// The byte position in the wiki text where the element starts.
+    pub start: usize,
+
+    /// The value of the attribute, if any.
+    pub value: Option<AttributeValue<'a>>,
+}
+
+/// Type of quotation marks around the value of an HTML attribute.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum AttributeQuote {
+    /// Parsed from a value enclosed in `"`.
+    Double,
+
+    /// Parsed from a value not enclosed in quotation marks.
+    None,
+
+    /// Parsed from a value enclosed in `'`.
+    Single,
+}
+
+/// Value of an HTML attribute.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AttributeValue<'a> {
+    /// The byte position in the wiki text where the value ends, excluding the closing quotation mark.
+    pub end: usize,
+
+    /// The type of quotation marks around the value.
+    pub quote: AttributeQuote,
+
+    /// The byte position in the wiki text where the value starts, excluding the opening quotation mark.
+    pub start: usize,
+
+    /// The value exactly as written, without character entities decoded.
+    pub value: Cow<'a, str>,
+}
+
 /// Configuration for the parser.
 ///
 /// A configuration to correctly parse a real wiki can be created with `Configuration::new`. A configuration for testing and quick and dirty prototyping can be created with `Default::default`.
 pub struct Configuration {
+    capital_links: bool,
     character_entities: Trie<char>,
+    image_options: Vec<(String, Option<String>, ImageOptionType)>,
+    interwiki: HashMap<String, (bool, Option<String>)>,
+    link_prefix_character_set: HashSet<char>,
     link_trail_character_set: HashSet<char>,
-    magic_words: Trie<()>,
+    magic_links: Vec<MagicLinkType>,
+    magic_word_ids: Vec<String>,
+    magic_words: Trie<usize>,
+    namespace_ids: HashMap<String, i32>,
+    namespace_names: HashMap<i32, String>,
     namespaces: Trie<Namespace>,
     protocols: Trie<()>,
     redirect_magic_words: Trie<()>,
     tag_name_map: HashMap<String, TagClass>,
+    variables: HashMap<String, String>,
 }
 
 /// List item of a definition list.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct DefinitionListItem<'a> {
     /// The byte position in the wiki text where the element ends.
     pub end: usize,
@@ -173,6 +253,7 @@ pub struct DefinitionListItem<'a> {
 
 /// Identifier for the type of a definition list item.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum DefinitionListItemType {
     /// Parsed from the code `:`.
     Details,
@@ -181,8 +262,40 @@ pub enum DefinitionListItemType {
     Term,
 }
 
+/// Output of expanding templates in wiki text.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Expansion {
+    /// The result of parsing the expanded wiki text.
+    pub output: Output<'static>,
+
+    /// The expanded wiki text. The positions of the nodes in the output refer to this text.
+    pub wiki_text: String,
+}
+
+/// Footnote of a page, listed by a `<references>` tag of its group, which gets its content from one or more `<ref>` tags.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Footnote {
+    /// The wiki text of the content of the footnote, or `None` if none of its `<ref>` tags has content.
+    pub content: Option<String>,
+
+    /// The wiki text of the content of the `<ref>` tags that continue the footnote with the `follow` attribute, in the order they appear.
+    pub continuations: Vec<String>,
+
+    /// The group of the footnote, which is empty for the default group.
+    pub group: String,
+
+    /// The name of the footnote, if it has one.
+    pub name: Option<String>,
+
+    /// The number of the footnote within its group, counting from 1 in the order footnotes are first used in the text, and starting over after each `<references>` tag of the group.
+    pub number: usize,
+}
+
 /// Function parameter.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FunctionParameter<'a> {
     /// The byte position in the wiki text where the element ends.
     pub end: usize,
@@ -194,8 +307,178 @@ pub struct FunctionParameter<'a> {
     pub value: Vec<Node<'a>>,
 }
 
+/// Image in a gallery. Parsed from a line in a `<gallery>` tag.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GalleryEntry<'a> {
+    /// The byte position in the wiki text where the element ends.
+    pub end: usize,
+
+    /// The options of the image, in the order they appear. The caption is the option of type [`ImageOptionType::Caption`], if any.
+    pub options: Vec<ImageOption<'a>>,
+
+    /// The byte position in the wiki text where the element starts.
+    pub start: usize,
+
+    /// The target of the image as written, such as `File:Example.jpg`, with comments left out. Code in the target, such as templates, is not parsed. The target is empty for a line that has options but nothing before the first `|`, such as `|alpha`, which Mediawiki doesn't display.
+    pub target: Cow<'a, str>,
+
+    /// The title of the image, as given by [`Configuration::file_title`]. As in Mediawiki, the target is in the file namespace whether or not it starts with the namespace. `None` if the target isn't a valid title, such as when it's empty or contains templates.
+    pub title: Option<Title>,
+}
+
+/// Horizontal alignment of an image.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ImageAlignment {
+    /// Parsed from the option `center`.
+    Center,
+
+    /// Parsed from the option `left`.
+    Left,
+
+    /// Parsed from the option `none`.
+    None,
+
+    /// Parsed from the option `right`.
+    Right,
+}
+
+/// Display format of an image.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ImageFormat {
+    /// Parsed from the option `frame`.
+    Frame,
+
+    /// Parsed from the option `frameless`.
+    Frameless,
+
+    /// Parsed from the option `thumb=` followed by the file name of the thumbnail.
+    ManualThumbnail,
+
+    /// Parsed from the option `thumb`.
+    Thumbnail,
+}
+
+/// Option of an image, separated from other options by `|`.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ImageOption<'a> {
+    /// The byte position in the wiki text where the element ends.
+    pub end: usize,
+
+    /// The byte position in the wiki text where the element starts.
+    pub start: usize,
+
+    /// The type of option.
+    pub type_: ImageOptionType,
+
+    /// The value of the option. For options that take a value, such as `alt=`, this excludes the keyword. For options that don't take a value, this is empty. For the caption and unrecognized options, this is the entire content of the option.
+    pub value: Vec<Node<'a>>,
+}
+
+/// Identifier for the type of an image option.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ImageOptionType {
+    /// Horizontal alignment. Parsed from options such as `left` and `center`.
+    Alignment(ImageAlignment),
+
+    /// Alternative text. Parsed from the option `alt=`.
+    Alt,
+
+    /// Border. Parsed from the option `border`.
+    Border,
+
+    /// The caption of the image. This is the last option that isn't recognized as any other type of option.
+    Caption,
+
+    /// CSS class. Parsed from the option `class=`.
+    Class,
+
+    /// Display format. Parsed from options such as `thumb` and `frame`.
+    Format(ImageFormat),
+
+    /// Language of an SVG file. Parsed from the option `lang=`.
+    Lang,
+
+    /// Link target. Parsed from the option `link=`.
+    Link,
+
+    /// Page of a multipage file. Parsed from the option `page=`.
+    Page,
+
+    /// Size in pixels. Parsed from options such as `200px` and `200x100px`.
+    Size,
+
+    /// Option that isn't recognized and isn't the caption.
+    Unrecognized,
+
+    /// Scale relative to the default thumbnail size. Parsed from the option `upright`, optionally followed by `=` and a factor.
+    Upright,
+
+    /// Vertical alignment. Parsed from options such as `top` and `text-bottom`.
+    VerticalAlignment(ImageVerticalAlignment),
+}
+
+/// Vertical alignment of an image.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ImageVerticalAlignment {
+    /// Parsed from the option `baseline`.
+    Baseline,
+
+    /// Parsed from the option `bottom`.
+    Bottom,
+
+    /// Parsed from the option `middle`.
+    Middle,
+
+    /// Parsed from the option `sub`.
+    Sub,
+
+    /// Parsed from the option `super`.
+    Super,
+
+    /// Parsed from the option `text-bottom`.
+    TextBottom,
+
+    /// Parsed from the option `text-top`.
+    TextTop,
+
+    /// Parsed from the option `top`.
+    Top,
+}
+
+/// Whether wiki text is parsed as a page being viewed or as a page being transcluded into another page, which determines what the tags `<includeonly>`, `<noinclude>` and `<onlyinclude>` leave out.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum IncludeMode {
+    /// The page is transcluded, such as a template. If the page has any `<onlyinclude>` tag, only the content between `<onlyinclude>` and `</onlyinclude>` is included. Otherwise the content between `<noinclude>` and `</noinclude>` is left out.
+    Transclusion,
+
+    /// The page is viewed on its own. The content between `<includeonly>` and `</includeonly>` is left out.
+    View,
+}
+
+/// Text outside the brackets of a link that is displayed as part of the link, either a link prefix right before `[[` or a link trail right after `]]`.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LinkAffix<'a> {
+    /// The byte position in the wiki text where the text ends.
+    pub end: usize,
+
+    /// The byte position in the wiki text where the text starts.
+    pub start: usize,
+
+    /// The text.
+    pub value: Cow<'a, str>,
+}
+
 /// List item of an ordered list or unordered list.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ListItem<'a> {
     /// The byte position in the wiki text where the element ends.
     pub end: usize,
@@ -207,8 +490,24 @@ pub struct ListItem<'a> {
     pub start: usize,
 }
 
+/// Identifier for the type of a magic link.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MagicLinkType {
+    /// Parsed from the code `ISBN` followed by an International Standard Book Number.
+    Isbn,
+
+    /// Parsed from the code `PMID` followed by a PubMed identifier.
+    Pmid,
+
+    /// Parsed from the code `RFC` followed by the number of a Request for Comments.
+    Rfc,
+}
+
 /// Parsed node.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
 pub enum Node<'a> {
     /// Toggle bold text. Parsed from the code `'''`.
     Bold {
@@ -240,10 +539,10 @@ pub enum Node<'a> {
         start: usize,
 
         /// The category referred to.
-        target: &'a str,
+        target: Cow<'a, str>,
     },
 
-    /// Character entity. Parsed from code starting with `&` and ending with `;`.
+    /// Character entity. Parsed from code starting with `&` and ending with `;`, either a named entity or a decimal or hexadecimal numeric character reference.
     CharacterEntity {
         /// The character represented.
         character: char,
@@ -288,16 +587,22 @@ pub enum Node<'a> {
         start: usize,
     },
 
-    /// External link. Parsed from code starting with `[` and a valid protocol.
+    /// External link. Parsed from code starting with `[` and a valid protocol, or when enabled by [`ParseOptions::bare_urls`], from a URL standing on its own in the text.
     ExternalLink {
+        /// Whether the link is enclosed in brackets, as opposed to being a bare URL.
+        bracketed: bool,
+
         /// The byte position in the wiki text where the element ends.
         end: usize,
 
-        /// The content of the element.
-        nodes: Vec<Node<'a>>,
+        /// The label of the link, which is empty if there is no label.
+        label: Vec<Node<'a>>,
 
         /// The byte position in the wiki text where the element starts.
         start: usize,
+
+        /// The URL exactly as written, starting with a protocol recognized by the configuration, which may be `//` for a protocol-relative URL. Code in the URL, such as templates and character entities, is not parsed.
+        url: Cow<'a, str>,
     },
 
     /// Function. Parsed from code starting with `{{`, containing a `:`, and ending with `}}`.
@@ -315,6 +620,24 @@ pub enum Node<'a> {
         start: usize,
     },
 
+    /// Gallery of images. Parsed from an extension tag with the content model [`TagContentModel::Gallery`], such as `<gallery>`, with each line of its content that isn't blank as an entry.
+    Gallery {
+        /// The attributes of the tag, such as `mode` and `widths`.
+        attributes: Vec<Attribute<'a>>,
+
+        /// The byte position in the wiki text where the element ends.
+        end: usize,
+
+        /// The images of the gallery, in the order they appear.
+        entries: Vec<GalleryEntry<'a>>,
+
+        /// The tag name.
+        name: Cow<'a, str>,
+
+        /// The byte position in the wiki text where the element starts.
+        start: usize,
+    },
+
     /// Heading. Parsed from code starting with `=` and ending with `=`.
     Heading {
         /// The byte position in the wiki text where the element ends.
@@ -339,19 +662,28 @@ pub enum Node<'a> {
         start: usize,
     },
 
+    /// Code left out in the include mode the wiki text is parsed in, as given by [`ParseOptions::include_mode`]: the tags `<includeonly>`, `<noinclude>` and `<onlyinclude>`, and the content they leave out.
+    Ignored {
+        /// The byte position in the wiki text where the element ends.
+        end: usize,
+
+        /// The byte position in the wiki text where the element starts.
+        start: usize,
+    },
+
     /// Image. Parsed from code starting with `[[`, a file namespace and `:`.
     Image {
         /// The byte position in the wiki text where the element ends.
         end: usize,
 
+        /// The options of the image, in the order they appear.
+        options: Vec<ImageOption<'a>>,
+
         /// The byte position in the wiki text where the element starts.
         start: usize,
 
         /// The file name of the image.
-        target: &'a str,
-
-        /// Additional information for the image.
-        text: Vec<Node<'a>>,
+        target: Cow<'a, str>,
     },
 
     /// Toggle italic text. Parsed from the code `''`.
@@ -363,29 +695,71 @@ pub enum Node<'a> {
         start: usize,
     },
 
-    /// Link. Parsed from code starting with `[[` and ending with `]]`.
-    Link {
+    /// Interlanguage link, which links the page to the same page in another language. Parsed from code starting with `[[`, an interwiki prefix of a language and `:`, and ending with `]]`.
+    LanguageLink {
         /// The byte position in the wiki text where the element ends.
         end: usize,
 
         /// The byte position in the wiki text where the element starts.
         start: usize,
 
+        /// The target of the link, including the interwiki prefix.
+        target: Cow<'a, str>,
+
+        /// The text after the pipe, if any, which is not displayed.
+        text: Vec<Node<'a>>,
+    },
+
+    /// Link. Parsed from code starting with `[[` and ending with `]]`, including the link prefix and link trail, if any.
+    Link {
+        /// The byte position in the wiki text where the element ends, after the link trail, if any.
+        end: usize,
+
+        /// The link prefix, made of characters that can appear in link prefixes right before `[[`, if any. Most languages have no link prefixes.
+        prefix: Option<LinkAffix<'a>>,
+
+        /// The byte position in the wiki text where the element starts, before the link prefix, if any.
+        start: usize,
+
         /// The target of the link.
-        target: &'a str,
+        target: Cow<'a, str>,
 
-        /// The text to display for the link.
+        /// The text between the pipe and `]]`, or the target if there is no pipe. The text displayed for the link is this text, or the target with the pipe trick applied if the text after the pipe is empty, surrounded by the link prefix and link trail.
         text: Vec<Node<'a>>,
 
+        /// The link trail, made of characters that can appear in link trails right after `]]`, if any.
+        trail: Option<LinkAffix<'a>>,
+
         /// whether the text was reparsed or not (start/end inside are then wrong).
         reparsed: bool,
     },
 
+    /// Magic link. Parsed from the code `ISBN`, `PMID` or `RFC` followed by whitespace and an identifier, if the type of magic link is enabled in the configuration.
+    MagicLink {
+        /// The byte position in the wiki text where the element ends.
+        end: usize,
+
+        /// The identifier, normalized to only its digits, and for an ISBN, an uppercase `X` as check digit, such as `9783161484100`.
+        id: Cow<'a, str>,
+
+        /// The identifier exactly as written, such as `978-3-16-148410-0`, which is the text the link displays after the keyword. It is written back as long as it normalizes to the identifier, and otherwise the identifier is written.
+        raw_id: Cow<'a, str>,
+
+        /// The byte position in the wiki text where the element starts.
+        start: usize,
+
+        /// The type of magic link.
+        type_: MagicLinkType,
+    },
+
     /// Magic word. Parsed from the code `__`, a valid magic word and `__`.
     MagicWord {
         /// The byte position in the wiki text where the element ends.
         end: usize,
 
+        /// The canonical name of the magic word the alias in the wiki text represents, such as `NOTOC`.
+        id: Cow<'a, str>,
+
         /// The byte position in the wiki text where the element starts.
         start: usize,
     },
@@ -444,7 +818,7 @@ pub enum Node<'a> {
         end: usize,
 
         /// The target of the redirect.
-        target: &'a str,
+        target: Cow<'a, str>,
 
         /// The byte position in the wiki text where the element starts.
         start: usize,
@@ -452,6 +826,9 @@ pub enum Node<'a> {
 
     /// Start tag. Parsed from code starting with `<` and a valid tag name.
     StartTag {
+        /// The HTML attributes of the element.
+        attributes: Vec<Attribute<'a>>,
+
         /// The byte position in the wiki text where the element ends.
         end: usize,
 
@@ -465,7 +842,7 @@ pub enum Node<'a> {
     /// Table. Parsed from code starting with `{|`.
     Table {
         /// The HTML attributes of the element.
-        attributes: Vec<Node<'a>>,
+        attributes: Vec<TableAttribute<'a>>,
 
         /// The captions of the table.
         captions: Vec<TableCaption<'a>>,
@@ -480,17 +857,23 @@ pub enum Node<'a> {
         start: usize,
     },
 
-    /// Extension tag. Parsed from code starting with `<` and the tag name of a valid extension tag.
+    /// Extension tag. Parsed from code starting with `<` and the tag name of a valid extension tag, other than a tag with the content model [`TagContentModel::Gallery`], which is parsed as [`Node::Gallery`].
     Tag {
+        /// The HTML attributes of the start tag.
+        attributes: Vec<Attribute<'a>>,
+
         /// The byte position in the wiki text where the element ends.
         end: usize,
 
         /// The tag name.
         name: Cow<'a, str>,
 
-        /// The content of the tag, between the start tag and the end tag, if any.
+        /// The content of the tag, between the start tag and the end tag, if any. Empty if the content model of the tag is raw text.
         nodes: Vec<Node<'a>>,
 
+        /// The content of the tag exactly as written, if the content model of the tag is raw text and the tag has an end tag.
+        raw_content: Option<RawContent<'a>>,
+
         /// The byte position in the wiki text where the element starts.
         start: usize,
     },
@@ -519,7 +902,7 @@ pub enum Node<'a> {
         start: usize,
 
         /// The text.
-        value: &'a str,
+        value: Cow<'a, str>,
     },
 
     /// Unordered list. Parsed from code starting with `*`.
@@ -533,10 +916,26 @@ pub enum Node<'a> {
         /// The byte position in the wiki text where the element starts.
         start: usize,
     },
+
+    /// Magic variable, such as `{{PAGENAME}}`. Parsed from code starting with `{{`, an alias of a variable and `}}`.
+    Variable {
+        /// The byte position in the wiki text where the element ends.
+        end: usize,
+
+        /// The ID of the variable the alias represents, such as `pagename`.
+        id: Cow<'a, str>,
+
+        /// The alias of the variable as written in the wiki text.
+        name: Cow<'a, str>,
+
+        /// The byte position in the wiki text where the element starts.
+        start: usize,
+    },
 }
 
 /// Output of parsing wiki text.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Output<'a> {
     /// The top level of parsed nodes.
     pub nodes: Vec<Node<'a>>,
@@ -547,6 +946,7 @@ pub struct Output<'a> {
 
 /// Template parameter.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Parameter<'a> {
     /// The byte position in the wiki text where the element ends.
     pub end: usize,
@@ -561,6 +961,19 @@ pub struct Parameter<'a> {
     pub value: Vec<Node<'a>>,
 }
 
+/// Options for parsing wiki text, given to [`Configuration::parse_with_options`].
+#[derive(Clone, Copy, Debug)]
+pub struct ParseOptions {
+    /// Whether URLs standing on their own in the text, such as `https://example.org/`, are parsed as external links, the way Mediawiki links them. Punctuation at the end of such a URL is not part of the link, and neither are `&lt;`, `&gt;`, templates and the `=` signs that end a heading.
+    pub bare_urls: bool,
+
+    /// Whether the wiki text is parsed as a page being viewed or being transcluded, which determines what the tags `<includeonly>`, `<noinclude>` and `<onlyinclude>` leave out. The tags and the content they leave out are parsed as [`Node::Ignored`], so that positions still refer to the wiki text as written.
+    pub include_mode: IncludeMode,
+
+    /// The maximum time the parsing may take before it is aborted, or zero for no limit.
+    pub max_duration: std::time::Duration,
+}
+
 /// Element that has a start position and end position.
 pub trait Positioned {
     /// The byte position in the wiki text where the element ends.
@@ -570,17 +983,112 @@ pub trait Positioned {
     fn start(&self) -> usize;
 }
 
+/// Content of an extension tag whose content model is raw text, which is not parsed.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RawContent<'a> {
+    /// The byte position in the wiki text where the element ends.
+    pub end: usize,
+
+    /// The byte position in the wiki text where the element starts.
+    pub start: usize,
+
+    /// The content exactly as written.
+    pub value: Cow<'a, str>,
+}
+
+/// Reference parsed from a `<ref>` tag.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Reference {
+    /// The wiki text of the content of the tag, or `None` if the tag has no content, which reuses the content of the footnote with the same name.
+    pub content: Option<String>,
+
+    /// The byte position in the wiki text where the element ends.
+    pub end: usize,
+
+    /// The value of the attribute `follow`, which is the name of the footnote the content of the tag continues, rather than the tag being a footnote of its own.
+    pub follow: Option<String>,
+
+    /// The index in [`References::footnotes`] of the footnote the tag belongs to, or `None` if the tag is in a `<references>` tag and no footnote with its name is used in the text.
+    pub footnote: Option<usize>,
+
+    /// The group of the reference, from the attribute `group` of the tag or of the `<references>` tag it is in. Empty for the default group.
+    pub group: String,
+
+    /// Whether the tag is in a `<references>` tag, which defines the content of a footnote used in the text, rather than being in the text.
+    pub list_defined: bool,
+
+    /// The value of the attribute `name`, if any.
+    pub name: Option<String>,
+
+    /// The byte position in the wiki text where the element starts.
+    pub start: usize,
+}
+
+/// Footnotes and references of a page, as given by [`Output::references`].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct References {
+    /// The footnotes, in the order they are first used in the text.
+    pub footnotes: Vec<Footnote>,
+
+    /// The references, in the order their `<ref>` tags appear.
+    pub references: Vec<Reference>,
+
+    /// Warnings about references that are used without being defined, named references that are defined with different content, and references defined in a `<references>` tag but never used.
+    pub warnings: Vec<Warning>,
+}
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 enum TagClass {
-    ExtensionTag,
+    ExtensionTag(TagContentModel),
     Tag,
 }
 
+/// HTML attribute of a table, table caption, table row or table cell.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TableAttribute<'a> {
+    /// The byte position in the wiki text where the element ends.
+    pub end: usize,
+
+    /// The name of the attribute exactly as written, which may differ in case from the canonical lowercase name.
+    pub name: Cow<'a, str>,
+
+    /// The content of the name if it contains code other than text, such as a template that gives attributes when expanded, and otherwise empty.
+    pub name_nodes: Vec<Node<'a>>,
+
+    /// The byte position in the wiki text where the element starts.
+    pub start: usize,
+
+    /// The value of the attribute, if any.
+    pub value: Option<TableAttributeValue<'a>>,
+}
+
+/// Value of an HTML attribute of a table, table caption, table row or table cell.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TableAttributeValue<'a> {
+    /// The byte position in the wiki text where the value ends, excluding the closing quotation mark.
+    pub end: usize,
+
+    /// The content of the value, which can contain templates and other code.
+    pub nodes: Vec<Node<'a>>,
+
+    /// The type of quotation marks around the value.
+    pub quote: AttributeQuote,
+
+    /// The byte position in the wiki text where the value starts, excluding the opening quotation mark.
+    pub start: usize,
+}
+
 /// Table caption.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TableCaption<'a> {
     /// The HTML attributes of the element.
-    pub attributes: Option<Vec<Node<'a>>>,
+    pub attributes: Option<Vec<TableAttribute<'a>>>,
 
     /// The content of the element.
     pub content: Vec<Node<'a>>,
@@ -594,9 +1102,10 @@ pub struct TableCaption<'a> {
 
 /// Table cell.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TableCell<'a> {
     /// The HTML attributes of the element.
-    pub attributes: Option<Vec<Node<'a>>>,
+    pub attributes: Option<Vec<TableAttribute<'a>>>,
 
     /// The content of the element.
     pub content: Vec<Node<'a>>,
@@ -613,6 +1122,7 @@ pub struct TableCell<'a> {
 
 /// Type of table cell.
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum TableCellType {
     /// Heading cell.
     Heading,
@@ -623,9 +1133,10 @@ pub enum TableCellType {
 
 /// Table row.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TableRow<'a> {
     /// The HTML attributes of the element.
-    pub attributes: Vec<Node<'a>>,
+    pub attributes: Vec<TableAttribute<'a>>,
 
     /// The cells in the row.
     pub cells: Vec<TableCell<'a>>,
@@ -636,3 +1147,42 @@ pub struct TableRow<'a> {
     /// The byte position in the wiki text where the element starts.
     pub start: usize,
 }
+
+/// How the content of an extension tag is parsed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TagContentModel {
+    /// The content is a list of images, one on each line, and the tag is parsed as [`Node::Gallery`]. Like with [`LineOriented`](Self::LineOriented), each line of the content stays a line of its own. Used for the tag `gallery`.
+    Gallery,
+
+    /// The content is wiki text, but syntax that depends on the start of a line, such as lists, headings and tables, is not recognized, so that each line of the content stays a line of its own. Used for tags such as `poem` and `imagemap`.
+    LineOriented,
+
+    /// The content is raw text that isn't parsed, such as the source code in `syntaxhighlight`. The content is given as the raw content of [`Node::Tag`].
+    Raw,
+
+    /// The content is wiki text, such as in `ref`.
+    Wikitext,
+}
+
+/// Normalized title of a page, such as the target of a link. Created by [`Configuration::title`].
+///
+/// Titles that refer to the same page compare equal, regardless of how they are written.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Title {
+    /// The part after `#`, if any, referring to a section of the page.
+    pub fragment: Option<String>,
+
+    /// The interwiki prefix in lowercase, if the title refers to a page on another wiki.
+    pub interwiki: Option<String>,
+
+    /// The number of the namespace of the page, 0 for the main namespace.
+    pub namespace: i32,
+
+    /// The name of the namespace of the page, an empty string for the main namespace.
+    pub namespace_name: String,
+
+    /// The name of the page without the namespace prefix.
+    pub page_name: String,
+}
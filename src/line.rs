@@ -38,7 +38,12 @@ pub fn parse_beginning_of_line(state: &mut crate::State, line_start_position: Op
                 let start_position = state.scan_position;
                 loop {
                     match state.get_byte(state.scan_position) {
-                        None => return,
+                        None => {
+                            if line_start_position.is_none() {
+                                state.flushed_position = state.scan_position;
+                            }
+                            return;
+                        }
                         Some(b'\n') => break,
                         Some(b'\t') | Some(b' ') => state.scan_position += 1,
                         Some(b'{') if state.get_byte(state.scan_position + 1) == Some(b'|') => {
@@ -244,7 +249,7 @@ fn parse_preformatted_end_of_line(state: &mut crate::State) {
     state.scan_position += 1;
     let nodes = std::mem::replace(&mut state.nodes, open_node.nodes);
     state.nodes.push(crate::Node::Preformatted {
-        end: state.scan_position,
+        end: state.scan_position.min(state.wiki_text.len()),
         nodes,
         start: open_node.start,
     });
@@ -18,30 +18,20 @@ pub fn parse_link_end<'a>(
     state.flush(inner_end_position);
     state.scan_position += 2;
     state.flushed_position = state.scan_position;
-    let mut text = std::mem::replace(&mut state.nodes, nodes);
+    let text = std::mem::replace(&mut state.nodes, nodes);
     let end = state.scan_position;
     let start = start_position;
-    state.nodes.push(match namespace {
+    let node = match namespace {
         None => {
-            let mut trail_end_position = end;
-            for character in state.wiki_text[end..].chars() {
-                if !configuration.link_trail_character_set.contains(&character) {
-                    break;
-                }
-                trail_end_position += character.len_utf8();
-            }
-            if trail_end_position > end {
-                text.push(crate::Node::Text {
-                    end: trail_end_position,
-                    start: end,
-                    value: &state.wiki_text[end..trail_end_position],
-                });
-            }
+            let prefix = parse_link_prefix(state, configuration, start);
+            let trail = parse_link_trail(state, configuration, end);
             crate::Node::Link {
-                end: trail_end_position,
-                start,
-                target,
+                end: trail.as_ref().map_or(end, |trail| trail.end),
+                start: prefix.as_ref().map_or(start, |prefix| prefix.start),
+                prefix,
+                target: crate::Cow::Borrowed(target),
                 text,
+                trail,
                 reparsed: false, // TODO
             }
         }
@@ -49,15 +39,22 @@ pub fn parse_link_end<'a>(
             end,
             ordinal: text,
             start,
-            target,
+            target: crate::Cow::Borrowed(target),
         },
         Some(crate::Namespace::File) => crate::Node::Image {
             end,
+            options: crate::image::parse_image_options(configuration, text, inner_end_position),
             start,
-            target,
+            target: crate::Cow::Borrowed(target),
+        },
+        Some(crate::Namespace::LanguageLink) => crate::Node::LanguageLink {
+            end,
+            start,
+            target: crate::Cow::Borrowed(target),
             text,
         },
-    });
+    };
+    state.nodes.push(node);
 }
 
 pub fn parse_link_start(state: &mut crate::State, configuration: &crate::Configuration) {
@@ -186,69 +183,133 @@ fn parse_end(
     }
     let start_position = state.scan_position;
     state.flush(start_position);
-    let trail_start_position = target_end_position + 2;
-    let mut trail_end_position = trail_start_position;
+    let end_position = target_end_position + 2;
+    state.flushed_position = end_position;
+    state.scan_position = end_position;
     let value = &state.wiki_text[target_start_position..target_end_position];
     match namespace {
         Some(crate::Namespace::Category) => {
             state.nodes.push(crate::Node::Category {
-                end: trail_end_position,
+                end: end_position,
                 ordinal: vec![],
-                start: state.scan_position,
-                target: value.trim_end(),
+                start: start_position,
+                target: crate::Cow::Borrowed(value.trim_end()),
             });
         }
         Some(crate::Namespace::File) => {
             state.nodes.push(crate::Node::Image {
-                end: trail_end_position,
-                start: state.scan_position,
-                target: value.trim_end(),
+                end: end_position,
+                options: vec![],
+                start: start_position,
+                target: crate::Cow::Borrowed(value.trim_end()),
+            });
+        }
+        Some(crate::Namespace::LanguageLink) => {
+            state.nodes.push(crate::Node::LanguageLink {
+                end: end_position,
+                start: start_position,
+                target: crate::Cow::Borrowed(value.trim_end()),
                 text: vec![],
             });
         }
         None => {
-            for character in state.wiki_text[trail_start_position..].chars() {
-                if !configuration.link_trail_character_set.contains(&character) {
-                    break;
-                }
-                trail_end_position += character.len_utf8();
-            }
-
+            let prefix = parse_link_prefix(state, configuration, start_position);
+            let trail = parse_link_trail(state, configuration, end_position);
             let target_text = crate::Node::Text {
                 end: target_end_position,
                 start: target_start_position,
-                value,
+                value: crate::Cow::Borrowed(value),
             };
             let text = if should_reparse {
-                let reparsed = crate::parse::parse(configuration, value, std::time::Duration::ZERO);
+                let reparsed = crate::parse::parse(
+                    configuration,
+                    value,
+                    &crate::ParseOptions {
+                        max_duration: std::time::Duration::ZERO,
+                        ..crate::ParseOptions::default()
+                    },
+                );
                 if let Ok(reparsed) = reparsed {
                     reparsed.nodes
                 } else {
                     vec![target_text]
                 }
-            } else if trail_end_position > trail_start_position {
-                vec![
-                    target_text,
-                    crate::Node::Text {
-                        end: trail_end_position,
-                        start: trail_start_position,
-                        value: &state.wiki_text[trail_start_position..trail_end_position],
-                    },
-                ]
             } else {
                 vec![target_text]
             };
             state.nodes.push(crate::Node::Link {
-                end: trail_end_position,
-                start: state.scan_position,
-                target: state.wiki_text[target_start_position..target_end_position].trim_end(),
+                end: trail.as_ref().map_or(end_position, |trail| trail.end),
+                start: prefix
+                    .as_ref()
+                    .map_or(start_position, |prefix| prefix.start),
+                prefix,
+                target: crate::Cow::Borrowed(value.trim_end()),
                 text,
+                trail,
                 reparsed: should_reparse,
             });
         }
     }
+}
+
+/// Splits the link prefix, if any, from the end of the text right before a link that starts at the given position.
+fn parse_link_prefix<'a>(
+    state: &mut crate::State<'a>,
+    configuration: &crate::Configuration,
+    start_position: usize,
+) -> Option<crate::LinkAffix<'a>> {
+    let wiki_text = state.wiki_text;
+    let Some(crate::Node::Text { end, start, value }) = state.nodes.last_mut() else {
+        return None;
+    };
+    if *end != start_position {
+        return None;
+    }
+    let prefix_length: usize = wiki_text[*start..*end]
+        .chars()
+        .rev()
+        .take_while(|character| configuration.link_prefix_character_set.contains(character))
+        .map(char::len_utf8)
+        .sum();
+    if prefix_length == 0 {
+        return None;
+    }
+    let prefix_start_position = *end - prefix_length;
+    if prefix_start_position == *start {
+        state.nodes.pop();
+    } else {
+        *end = prefix_start_position;
+        *value = crate::Cow::Borrowed(&wiki_text[*start..prefix_start_position]);
+    }
+    Some(crate::LinkAffix {
+        end: start_position,
+        start: prefix_start_position,
+        value: crate::Cow::Borrowed(&wiki_text[prefix_start_position..start_position]),
+    })
+}
+
+/// Parses the link trail, if any, after a link that ends at the given position.
+fn parse_link_trail<'a>(
+    state: &mut crate::State<'a>,
+    configuration: &crate::Configuration,
+    end_position: usize,
+) -> Option<crate::LinkAffix<'a>> {
+    let trail_length: usize = state.wiki_text[end_position..]
+        .chars()
+        .take_while(|character| configuration.link_trail_character_set.contains(character))
+        .map(char::len_utf8)
+        .sum();
+    if trail_length == 0 {
+        return None;
+    }
+    let trail_end_position = end_position + trail_length;
     state.flushed_position = trail_end_position;
     state.scan_position = trail_end_position;
+    Some(crate::LinkAffix {
+        end: trail_end_position,
+        start: end_position,
+        value: crate::Cow::Borrowed(&state.wiki_text[end_position..trail_end_position]),
+    })
 }
 
 fn parse_unexpected_end(state: &mut crate::State, target_end_position: usize) {
@@ -259,3 +320,48 @@ fn parse_unexpected_end(state: &mut crate::State, target_end_position: usize) {
     });
     state.scan_position += 1;
 }
+
+/// Returns the text displayed for a link with nothing after the pipe, the way the pipe trick of Mediawiki gives it: the target without a namespace or interwiki prefix, without a part in parentheses at the end and then without a part after a comma at the end, such as `Paris` for `Paris (Texas), United States`.
+pub fn pipe_trick(target: &str) -> &str {
+    let target = target.trim();
+    let target = target.strip_prefix(':').unwrap_or(target);
+    let target = match target.split_once(':') {
+        Some((prefix, rest))
+            if prefix.chars().all(|character| {
+                character.is_ascii_alphanumeric()
+                    || !character.is_ascii()
+                    || matches!(character, ' ' | '-' | '_')
+            }) =>
+        {
+            rest
+        }
+        _ => target,
+    };
+    // Like in Mediawiki, the shortest start of the name is kept that the parts to strip can follow.
+    target
+        .char_indices()
+        .skip(1)
+        .map(|(position, _)| position)
+        .find(|position| is_pipe_trick_suffix(&target[*position..]))
+        .map_or(target, |position| &target[..position])
+}
+
+/// Checks that the text is a part in parentheses, a part after a comma, or a part in parentheses followed by a part after a comma, the parts that the pipe trick strips from the end of a name.
+fn is_pipe_trick_suffix(text: &str) -> bool {
+    let is_comma_part = |text: &str| {
+        [", ", "，"].iter().any(|comma| {
+            text.strip_prefix(comma)
+                .is_some_and(|rest| !rest.is_empty())
+        })
+    };
+    if is_comma_part(text) {
+        return true;
+    }
+    let Some(rest) = text.strip_prefix(" (").or_else(|| text.strip_prefix('(')) else {
+        return false;
+    };
+    rest.match_indices(')').any(|(position, _)| {
+        let after = &rest[position + 1..];
+        position > 0 && (after.is_empty() || is_comma_part(after))
+    })
+}
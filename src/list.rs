@@ -3,7 +3,10 @@
 // the file LICENSE at the top-level directory of this distribution.
 
 pub fn parse_list_end_of_line(state: &mut crate::State) {
-    let item_end_position = state.skip_whitespace_backwards(state.scan_position);
+    // Whitespace that was already consumed, such as by a comment, is part of the item.
+    let item_end_position = state
+        .skip_whitespace_backwards(state.scan_position)
+        .max(state.flushed_position);
     state.flush(item_end_position);
     state.scan_position += 1;
     let mut level = 0;
@@ -0,0 +1,251 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+impl crate::MagicLinkType {
+    /// Returns the code that starts a magic link of the type, such as `ISBN`.
+    #[must_use]
+    pub fn keyword(self) -> &'static str {
+        match self {
+            crate::MagicLinkType::Isbn => "ISBN",
+            crate::MagicLinkType::Pmid => "PMID",
+            crate::MagicLinkType::Rfc => "RFC",
+        }
+    }
+
+    /// Returns the URL of the page a magic link with the given identifier refers to, the way Mediawiki links it, or `None` for an ISBN, which refers to the page `Special:BookSources` on the wiki itself.
+    ///
+    /// ```
+    /// use parse_wiki_text_2::MagicLinkType;
+    ///
+    /// assert_eq!(
+    ///     MagicLinkType::Rfc.url("2616").unwrap(),
+    ///     "https://datatracker.ietf.org/doc/html/rfc2616"
+    /// );
+    /// assert_eq!(MagicLinkType::Isbn.url("9783161484100"), None);
+    /// ```
+    #[must_use]
+    pub fn url(self, id: &str) -> Option<String> {
+        match self {
+            crate::MagicLinkType::Isbn => None,
+            crate::MagicLinkType::Pmid => Some(format!("https://pubmed.ncbi.nlm.nih.gov/{}/", id)),
+            crate::MagicLinkType::Rfc => {
+                Some(format!("https://datatracker.ietf.org/doc/html/rfc{}", id))
+            }
+        }
+    }
+}
+
+/// Checks whether the code is a magic link of the given type with the given identifier as written.
+pub fn is_magic_link(code: &str, type_: crate::MagicLinkType, raw_id: &str) -> bool {
+    let Some(rest) = code.strip_prefix(type_.keyword()) else {
+        return false;
+    };
+    let identifier_start_position = spaces_length(rest);
+    identifier_start_position > 0 && &rest[identifier_start_position..] == raw_id
+}
+
+/// Checks whether an ISBN, normalized to its digits and check digit, has a valid check digit.
+fn is_valid_isbn(id: &str) -> bool {
+    let digits: Vec<u32> = id
+        .chars()
+        .map(|character| character.to_digit(10).unwrap_or(10))
+        .collect();
+    match digits.len() {
+        10 => {
+            digits
+                .iter()
+                .zip((1..=10).rev())
+                .map(|(digit, weight)| digit * weight)
+                .sum::<u32>()
+                % 11
+                == 0
+        }
+        13 => {
+            digits[12] < 10
+                && digits
+                    .iter()
+                    .zip([1, 3].iter().cycle())
+                    .map(|(digit, weight)| digit * weight)
+                    .sum::<u32>()
+                    % 10
+                    == 0
+        }
+        _ => false,
+    }
+}
+
+/// Returns the length of the ISBN the text starts with, matching `(97[89]-?)?([0-9]-?){9}[0-9Xx]` with any space allowed where a dash is, the way Mediawiki matches it, if it doesn't continue with a word character.
+fn isbn_length(text: &str) -> Option<usize> {
+    if text.starts_with("978") || text.starts_with("979") {
+        let prefix_length = 3 + separator_length(&text[3..]);
+        if let Some(length) = isbn_digits_length(&text[prefix_length..]) {
+            return Some(prefix_length + length);
+        }
+    }
+    isbn_digits_length(text)
+}
+
+/// Returns the length of the nine digits and the check digit of an ISBN that the text starts with, if it doesn't continue with a word character.
+fn isbn_digits_length(text: &str) -> Option<usize> {
+    let mut position = 0;
+    for _ in 0..9 {
+        if !text.as_bytes().get(position)?.is_ascii_digit() {
+            return None;
+        }
+        position += 1;
+        position += separator_length(&text[position..]);
+    }
+    match text.as_bytes().get(position)? {
+        b'0'..=b'9' | b'X' | b'x' if !starts_with_word_character(&text[position + 1..]) => {
+            Some(position + 1)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the normalized identifier of the code of a magic link after the keyword and spaces: its digits, and an uppercase `X` as check digit of an ISBN.
+pub fn normalize(code: &str) -> String {
+    let mut id = String::new();
+    let mut position = 0;
+    while let Some(character) = code[position..].chars().next() {
+        match separator_length(&code[position..]) {
+            0 => {
+                id.push(character.to_ascii_uppercase());
+                position += character.len_utf8();
+            }
+            length => position += length,
+        }
+    }
+    id
+}
+
+/// Parses a magic link at the scan position, if the text there starts with the keyword of a type of magic link enabled in the configuration. Returns whether a magic link was parsed.
+///
+/// As in Mediawiki, a magic link must stand on its own, not attached to a word before or after it, and is not parsed inside the text of a link or an external link.
+pub fn parse_magic_link(state: &mut crate::State, configuration: &crate::Configuration) -> bool {
+    if configuration.magic_links.is_empty() {
+        return false;
+    }
+    let start_position = state.scan_position;
+    let Some(&type_) = configuration
+        .magic_links
+        .iter()
+        .find(|type_| state.wiki_text[start_position..].starts_with(type_.keyword()))
+    else {
+        return false;
+    };
+    if state.wiki_text[..start_position]
+        .chars()
+        .next_back()
+        .is_some_and(is_word_character)
+        || state.stack.iter().any(|open_node| {
+            matches!(
+                open_node.type_,
+                crate::OpenNodeType::ExternalLink { .. }
+                    | crate::OpenNodeType::Link {
+                        namespace: None,
+                        ..
+                    }
+            )
+        })
+    {
+        return false;
+    }
+    let keyword_end_position = start_position + type_.keyword().len();
+    let identifier_start_position =
+        keyword_end_position + spaces_length(&state.wiki_text[keyword_end_position..]);
+    if identifier_start_position == keyword_end_position {
+        return false;
+    }
+    let text = &state.wiki_text[identifier_start_position..];
+    let length = match type_ {
+        crate::MagicLinkType::Isbn => isbn_length(text),
+        crate::MagicLinkType::Pmid | crate::MagicLinkType::Rfc => {
+            let length = text.bytes().take_while(u8::is_ascii_digit).count();
+            if length == 0 || starts_with_word_character(&text[length..]) {
+                None
+            } else {
+                Some(length)
+            }
+        }
+    };
+    let Some(length) = length else {
+        return false;
+    };
+    let end_position = identifier_start_position + length;
+    let raw_id = &state.wiki_text[identifier_start_position..end_position];
+    let id = normalize(raw_id);
+    if type_ == crate::MagicLinkType::Isbn && !is_valid_isbn(&id) {
+        state.warnings.push(crate::Warning {
+            end: end_position,
+            message: crate::WarningMessage::InvalidIsbnChecksum,
+            start: start_position,
+        });
+    }
+    state.flush(start_position);
+    state.nodes.push(crate::Node::MagicLink {
+        end: end_position,
+        id: crate::Cow::Owned(id),
+        raw_id: crate::Cow::Borrowed(raw_id),
+        start: start_position,
+        type_,
+    });
+    state.flushed_position = end_position;
+    state.scan_position = end_position;
+    true
+}
+
+/// Returns the length of the dash or space the text starts with, or zero if it starts with neither.
+fn separator_length(text: &str) -> usize {
+    if text.starts_with('-') {
+        1
+    } else {
+        space_length(text)
+    }
+}
+
+/// Returns the length of the space the text starts with, or zero if it doesn't start with a space. A space is a whitespace character other than a line break, or a character entity of a non-breaking space.
+fn space_length(text: &str) -> usize {
+    if text.starts_with("&nbsp;") {
+        return 6;
+    }
+    if let Some(rest) = text.strip_prefix("&#") {
+        let (rest, number) = match rest.strip_prefix(['X', 'x']) {
+            None => (rest, "160;"),
+            Some(rest) => (rest, "a0;"),
+        };
+        let zeros = rest.bytes().take_while(|byte| *byte == b'0').count();
+        return if rest[zeros..]
+            .get(..number.len())
+            .is_some_and(|code| code.eq_ignore_ascii_case(number))
+        {
+            text.len() - rest.len() + zeros + number.len()
+        } else {
+            0
+        };
+    }
+    match text.chars().next() {
+        Some(character) if character != '\n' && character.is_whitespace() => character.len_utf8(),
+        _ => 0,
+    }
+}
+
+/// Returns the length of the spaces the text starts with.
+fn spaces_length(text: &str) -> usize {
+    let mut position = 0;
+    loop {
+        match space_length(&text[position..]) {
+            0 => return position,
+            length => position += length,
+        }
+    }
+}
+
+fn is_word_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+fn starts_with_word_character(text: &str) -> bool {
+    text.chars().next().is_some_and(is_word_character)
+}
@@ -3,7 +3,7 @@
 // the file LICENSE at the top-level directory of this distribution.
 
 pub fn parse_magic_word(state: &mut crate::State, configuration: &crate::Configuration) {
-    if let Ok((match_length, _)) = configuration
+    if let Ok((match_length, index)) = configuration
         .magic_words
         .find(&state.wiki_text[state.scan_position + 2..])
     {
@@ -16,6 +16,7 @@ pub fn parse_magic_word(state: &mut crate::State, configuration: &crate::Configu
             state.flushed_position = end_position + 2;
             state.nodes.push(crate::Node::MagicWord {
                 end: state.flushed_position,
+                id: crate::Cow::Owned(configuration.magic_word_ids[index].clone()),
                 start: state.scan_position,
             });
             state.scan_position = state.flushed_position;
@@ -24,3 +25,19 @@ pub fn parse_magic_word(state: &mut crate::State, configuration: &crate::Configu
     }
     state.scan_position += 1;
 }
+
+/// Checks whether the code is a magic word with an alias that can represent the magic word with the given canonical name.
+pub fn is_magic_word(code: &str, id: &str) -> bool {
+    let Some(alias) = code
+        .strip_prefix("__")
+        .and_then(|code| code.strip_suffix("__"))
+        .filter(|alias| !alias.is_empty())
+    else {
+        return false;
+    };
+    // The aliases of the configuration the node was parsed with aren't known here, but the canonical name of a magic word never is an alias of another magic word.
+    alias.to_lowercase() == id.to_lowercase()
+        || !crate::default::MAGIC_WORDS
+            .iter()
+            .any(|(name, _)| alias.eq_ignore_ascii_case(name))
+}
@@ -0,0 +1,209 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+const DAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Source of the values of magic variables, such as `{{PAGENAME}}` and `{{CURRENTYEAR}}`, for the page being processed.
+pub trait PageContext {
+    /// Returns the current time in seconds since the Unix epoch. Returning a fixed time gives results that don't depend on when the wiki text is processed, such as for tests.
+    fn current_time(&self) -> i64;
+
+    /// Returns the name of the namespace of the page, or an empty string for the main namespace.
+    fn namespace(&self) -> &str;
+
+    /// Returns the title of the page without the namespace prefix.
+    fn page_name(&self) -> &str;
+
+    /// Returns the value of the magic variable with the given ID, or `None` if the variable isn't known.
+    ///
+    /// The default implementation gives the values of the variables `!`, `basepagename`, `currentday`, `currentday2`, `currentdayname`, `currentdow`, `currenthour`, `currentmonth`, `currentmonth1`, `currentmonthabbrev`, `currentmonthname`, `currenttime`, `currenttimestamp`, `currentweek`, `currentyear`, `fullpagename`, `fullpagenamee`, `namespace`, `namespacee`, `pagename`, `pagenamee`, `rootpagename` and `subpagename` the same as Mediawiki does, with times in UTC and with `/` separating subpages in every namespace. Override it to support other variables.
+    fn variable(&self, id: &str) -> Option<String> {
+        variable(self, id)
+    }
+}
+
+/// Page context with fixed values, such as for tests.
+///
+/// ```
+/// use parse_wiki_text_2::{Page, PageContext};
+///
+/// let page = Page {
+///     current_time: 1_000_000_000,
+///     namespace: "Help",
+///     page_name: "Contents/Editing",
+/// };
+/// assert_eq!(page.variable("fullpagename").unwrap(), "Help:Contents/Editing");
+/// assert_eq!(page.variable("subpagename").unwrap(), "Editing");
+/// assert_eq!(page.variable("currenttimestamp").unwrap(), "20010909014640");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Page<'a> {
+    /// The current time in seconds since the Unix epoch.
+    pub current_time: i64,
+
+    /// The name of the namespace of the page, or an empty string for the main namespace.
+    pub namespace: &'a str,
+
+    /// The title of the page without the namespace prefix.
+    pub page_name: &'a str,
+}
+
+impl PageContext for Page<'_> {
+    fn current_time(&self) -> i64 {
+        self.current_time
+    }
+
+    fn namespace(&self) -> &str {
+        self.namespace
+    }
+
+    fn page_name(&self) -> &str {
+        self.page_name
+    }
+}
+
+/// Returns the value of a variable the way the default implementation of [`PageContext::variable`] does.
+fn variable(context: &(impl PageContext + ?Sized), id: &str) -> Option<String> {
+    let page_name = context.page_name();
+    let namespace = context.namespace();
+    let full_page_name = || {
+        if namespace.is_empty() {
+            page_name.to_string()
+        } else {
+            format!("{}:{}", namespace, page_name)
+        }
+    };
+    let time = context.current_time();
+    let days = time.div_euclid(86400);
+    let seconds = time.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+    let day_of_week = (days + 4).rem_euclid(7) as usize;
+    Some(match id {
+        "!" => "|".into(),
+        "basepagename" => page_name
+            .rsplit_once('/')
+            .map_or(page_name, |(base, _)| base)
+            .into(),
+        "currentday" => day.to_string(),
+        "currentday2" => format!("{:02}", day),
+        "currentdayname" => DAY_NAMES[day_of_week].into(),
+        "currentdow" => day_of_week.to_string(),
+        "currenthour" => format!("{:02}", seconds / 3600),
+        "currentmonth" => format!("{:02}", month),
+        "currentmonth1" => month.to_string(),
+        "currentmonthabbrev" => MONTH_NAMES[month as usize - 1][..3].into(),
+        "currentmonthname" => MONTH_NAMES[month as usize - 1].into(),
+        "currenttime" => format!("{:02}:{:02}", seconds / 3600, seconds / 60 % 60),
+        "currenttimestamp" => format!(
+            "{:04}{:02}{:02}{:02}{:02}{:02}",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        ),
+        "currentweek" => iso_week(days).to_string(),
+        "currentyear" => year.to_string(),
+        "fullpagename" => full_page_name(),
+        "fullpagenamee" => url_encode(&full_page_name()),
+        "namespace" => namespace.into(),
+        "namespacee" => url_encode(namespace),
+        "pagename" => page_name.into(),
+        "pagenamee" => url_encode(page_name),
+        "rootpagename" => page_name.split('/').next().unwrap_or(page_name).into(),
+        "subpagename" => page_name.rsplit('/').next().unwrap_or(page_name).into(),
+        _ => return None,
+    })
+}
+
+/// Converts a number of days since the Unix epoch to a year, month and day of the proleptic Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Returns the ISO 8601 week number of the day with the given number of days since the Unix epoch.
+fn iso_week(days: i64) -> i64 {
+    // Weeks belong to the year of their Thursday.
+    let day_of_week = (days + 3).rem_euclid(7);
+    let thursday = days - day_of_week + 3;
+    let (year, _, _) = civil_from_days(thursday);
+    let first_day = days_from_year_start(year);
+    (thursday - first_day) / 7 + 1
+}
+
+/// Returns the number of days since the Unix epoch of the first day of a year.
+fn days_from_year_start(year: i64) -> i64 {
+    let year = year - 1;
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    // Days are counted from the 1st of March, so that the leap day is the last day of the year.
+    era * 146097 + year_of_era * 365 + year_of_era / 4 - year_of_era / 100 - 719468 + 306
+}
+
+/// Encodes a page name for use in a URL the way Mediawiki does, with underscores for spaces.
+pub fn url_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b' ' => encoded.push('_'),
+            b'0'..=b'9'
+            | b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'!'
+            | b'$'
+            | b'('
+            | b')'
+            | b'*'
+            | b','
+            | b'-'
+            | b'.'
+            | b'/'
+            | b':'
+            | b';'
+            | b'@'
+            | b'_'
+            | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -19,8 +19,9 @@ pub enum ParseError<'a> {
 pub fn parse<'a>(
     configuration: &crate::Configuration,
     wiki_text: &'a str,
-    max_duration: std::time::Duration,
+    options: &crate::ParseOptions,
 ) -> Result<crate::Output<'a>, ParseError<'a>> {
+    let max_duration = options.max_duration;
     let mut state = crate::State {
         flushed_position: 0,
         nodes: vec![],
@@ -29,10 +30,13 @@ pub fn parse<'a>(
         warnings: vec![],
         wiki_text,
     };
+    if options.include_mode == crate::IncludeMode::Transclusion {
+        crate::inclusion::parse_only_include_start(&mut state);
+    }
     // parse redirects and warn about repeated newlines
     {
         let mut has_line_break = false;
-        let mut position = 0;
+        let mut position = state.scan_position;
         loop {
             match state.get_byte(position) {
                 Some(b'\n') => {
@@ -91,9 +95,26 @@ pub fn parse<'a>(
                 });
                 state.scan_position += 1;
             }
-            Some(b'\n') => {
-                crate::line::parse_end_of_line(&mut state);
-            }
+            Some(b'\n') => match state.stack.last() {
+                // External links end at the end of the line, even in a line oriented tag.
+                Some(crate::OpenNode {
+                    type_: crate::OpenNodeType::ExternalLink { .. },
+                    ..
+                }) => crate::line::parse_end_of_line(&mut state),
+                _ if state.stack.iter().any(|open_node| match &open_node.type_ {
+                    crate::OpenNodeType::Tag { name, .. } => matches!(
+                        configuration.tag_name_map.get(name.as_ref()),
+                        Some(crate::TagClass::ExtensionTag(
+                            crate::TagContentModel::Gallery | crate::TagContentModel::LineOriented,
+                        ))
+                    ),
+                    _ => false,
+                }) =>
+                {
+                    state.scan_position += 1;
+                }
+                _ => crate::line::parse_end_of_line(&mut state),
+            },
             Some(b'!')
                 if state.get_byte(state.scan_position + 1) == Some(b'!')
                     && matches!(
@@ -125,16 +146,20 @@ pub fn parse<'a>(
                 }
                 _ => state.scan_position += 1,
             },
-            Some(b'<') => match state.get_byte(state.scan_position + 1) {
-                Some(b'!')
-                    if state.get_byte(state.scan_position + 2) == Some(b'-')
-                        && state.get_byte(state.scan_position + 3) == Some(b'-') =>
-                {
-                    crate::comment::parse_comment(&mut state)
+            Some(b'<') => {
+                if !crate::inclusion::parse_inclusion_tag(&mut state, options.include_mode) {
+                    match state.get_byte(state.scan_position + 1) {
+                        Some(b'!')
+                            if state.get_byte(state.scan_position + 2) == Some(b'-')
+                                && state.get_byte(state.scan_position + 3) == Some(b'-') =>
+                        {
+                            crate::comment::parse_comment(&mut state)
+                        }
+                        Some(b'/') => crate::tag::parse_end_tag(&mut state, configuration),
+                        _ => crate::tag::parse_start_tag(&mut state, configuration),
+                    }
                 }
-                Some(b'/') => crate::tag::parse_end_tag(&mut state, configuration),
-                _ => crate::tag::parse_start_tag(&mut state, configuration),
-            },
+            }
             Some(b'=') => {
                 crate::template::parse_parameter_name_end(&mut state);
             }
@@ -150,9 +175,9 @@ pub fn parse<'a>(
                 Some(crate::OpenNode {
                     nodes,
                     start,
-                    type_: crate::OpenNodeType::ExternalLink,
+                    type_: crate::OpenNodeType::ExternalLink { url },
                 }) => {
-                    crate::external_link::parse_external_link_end(&mut state, start, nodes);
+                    crate::external_link::parse_external_link_end(&mut state, start, nodes, url);
                 }
                 Some(crate::OpenNode {
                     nodes,
@@ -235,11 +260,20 @@ pub fn parse<'a>(
             },
             Some(b'}') => {
                 if state.get_byte(state.scan_position + 1) == Some(b'}') {
-                    crate::template::parse_template_end(&mut state);
+                    crate::template::parse_template_end(&mut state, configuration);
                 } else {
                     state.scan_position += 1;
                 }
             }
+            Some(b'A'..=b'Z' | b'a'..=b'z') => {
+                if !crate::magic_link::parse_magic_link(&mut state, configuration) {
+                    if options.bare_urls {
+                        crate::external_link::parse_bare_url(&mut state, configuration);
+                    } else {
+                        state.scan_position += 1;
+                    }
+                }
+            }
             _ => {
                 state.scan_position += 1;
             }
@@ -0,0 +1,343 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+use crate::HashMap;
+
+/// Names of the built-in parser functions.
+const BUILT_IN_NAMES: &[&str] = &[
+    "#expr", "#if", "#ifeq", "#iferror", "#ifexpr", "#switch", "#tag",
+];
+
+/// Function that expands the nodes of an argument of a parser function and returns the resulting wiki text.
+type Expand<'e, 'a> = dyn FnMut(Vec<crate::Node<'a>>) -> String + 'e;
+
+/// Function registered to be called from wiki text, taking the expanded arguments and returning wiki text.
+type Function = dyn Fn(&[String]) -> String;
+
+/// Parser functions that can be called from wiki text in addition to the built-in ones.
+///
+/// The built-in functions are `#expr`, `#if`, `#ifeq`, `#iferror`, `#ifexpr`, `#switch` and `#tag`, which work the same as the functions of the ParserFunctions extension of Mediawiki.
+#[derive(Default)]
+pub struct ParserFunctions {
+    functions: HashMap<String, Box<Function>>,
+}
+
+impl ParserFunctions {
+    /// Creates a set of parser functions without any functions other than the built-in ones.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a parser function under the name written in the function call, such as `#name` for `{{#name:...}}`. Names are matched without regard to case. A function registered with the name of a built-in function or a previously registered function replaces that function.
+    ///
+    /// As in Mediawiki, the function is called with the arguments expanded and with whitespace trimmed, the first argument being the text between the colon and the first pipe, and returns the wiki text that replaces the function call. The returned wiki text is not expanded further.
+    ///
+    /// ```
+    /// use parse_wiki_text_2::{Configuration, ExpansionContext, Page, ParserFunctions};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut functions = ParserFunctions::new();
+    /// functions.register("#join", |arguments| arguments.join(", "));
+    /// let context = ExpansionContext {
+    ///     functions: &functions,
+    ///     page: &Page {
+    ///         current_time: 0,
+    ///         namespace: "",
+    ///         page_name: "Example",
+    ///     },
+    ///     templates: &HashMap::new(),
+    /// };
+    /// let expansion = Configuration::default()
+    ///     .expand("{{#JOIN: a | b }}", &context)
+    ///     .unwrap();
+    /// assert_eq!(expansion.wiki_text, "a, b");
+    /// ```
+    pub fn register(&mut self, name: &str, function: impl Fn(&[String]) -> String + 'static) {
+        self.functions
+            .insert(name.to_lowercase(), Box::new(function));
+    }
+
+    /// Checks whether there is a built-in or registered function with the given name.
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.functions.contains_key(&name) || BUILT_IN_NAMES.contains(&name.as_str())
+    }
+
+    /// Evaluates the function with the given name, which must be a built-in or registered function, and returns the wiki text that replaces the function call.
+    ///
+    /// The arguments are expanded only as needed, so that for example only the chosen branch of `#if` is expanded.
+    pub(crate) fn call<'a>(
+        &self,
+        name: &str,
+        parameters: Vec<crate::FunctionParameter<'a>>,
+        expand: &mut Expand<'_, 'a>,
+    ) -> String {
+        let name = name.to_lowercase();
+        if let Some(function) = self.functions.get(&name) {
+            let arguments: Vec<_> = parameters
+                .into_iter()
+                .map(|parameter| expand(parameter.value).trim().to_string())
+                .collect();
+            return function(&arguments);
+        }
+        let count = parameters.len();
+        let mut arguments: Vec<_> = parameters
+            .into_iter()
+            .map(|parameter| Some(parameter.value))
+            .collect();
+        let mut argument = |index: usize| match arguments.get_mut(index).and_then(Option::take) {
+            None => String::new(),
+            Some(nodes) => expand(nodes).trim().to_string(),
+        };
+        match name.as_str() {
+            "#expr" => match crate::expression::evaluate_expression(&argument(0)) {
+                Err(message) => error(&message),
+                Ok(None) => String::new(),
+                Ok(Some(value)) => crate::expression::format_number(value),
+            },
+            "#if" => {
+                if argument(0).is_empty() {
+                    argument(2)
+                } else {
+                    argument(1)
+                }
+            }
+            "#ifeq" => {
+                if loose_equals(&argument(0), &argument(1)) {
+                    argument(2)
+                } else {
+                    argument(3)
+                }
+            }
+            "#iferror" => {
+                let test = argument(0);
+                if is_error(&test) {
+                    argument(1)
+                } else if count > 2 {
+                    argument(2)
+                } else {
+                    test
+                }
+            }
+            "#ifexpr" => match crate::expression::evaluate_expression(&argument(0)) {
+                Err(message) => error(&message),
+                Ok(Some(value)) if value != 0.0 => argument(1),
+                Ok(_) => argument(2),
+            },
+            "#switch" => {
+                let value = argument(0);
+                switch(&value, arguments.into_iter().skip(1).flatten(), expand)
+            }
+            "#tag" => {
+                let name = argument(0);
+                let content = arguments
+                    .get_mut(1)
+                    .and_then(Option::take)
+                    .map(&mut *expand);
+                tag(
+                    &name,
+                    content,
+                    arguments.into_iter().skip(2).flatten(),
+                    expand,
+                )
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Formats an error message the way the ParserFunctions extension does.
+fn error(message: &str) -> String {
+    format!("<strong class=\"error\">{}</strong>", message)
+}
+
+/// Checks whether wiki text contains an error message, as generated for example by `#expr` or by a template loop.
+fn is_error(text: &str) -> bool {
+    let mut rest = text;
+    while let Some(position) = rest.find('<') {
+        rest = &rest[position + 1..];
+        let Some(name_end) = rest.find(|character: char| !character.is_ascii_alphabetic()) else {
+            break;
+        };
+        if !matches!(
+            rest[..name_end].to_ascii_lowercase().as_str(),
+            "div" | "p" | "span" | "strong"
+        ) {
+            continue;
+        }
+        let tag = rest[..rest.find('>').unwrap_or(rest.len())].to_ascii_lowercase();
+        let mut attributes = tag[name_end..].split("class=\"").skip(1);
+        if attributes.any(|classes| {
+            classes
+                .split('"')
+                .next()
+                .is_some_and(|classes| classes.split_whitespace().any(|class| class == "error"))
+        }) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Compares two values the way PHP compares strings with the `==` operator, which compares them as numbers if both are numeric.
+fn loose_equals(left: &str, right: &str) -> bool {
+    match (parse_numeric(left), parse_numeric(right)) {
+        (Some(left), Some(right)) => left == right,
+        _ => left == right,
+    }
+}
+
+/// Parses a string that PHP considers numeric.
+fn parse_numeric(text: &str) -> Option<f64> {
+    let text = text.trim();
+    if text.is_empty()
+        || !text
+            .chars()
+            .all(|character| matches!(character, '0'..='9' | '.' | 'e' | 'E' | '+' | '-'))
+    {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Returns part of a text between byte positions, borrowing the text if possible.
+fn slice<'a>(text: &crate::Cow<'a, str>, start: usize, end: usize) -> crate::Cow<'a, str> {
+    match text {
+        crate::Cow::Borrowed(text) => crate::Cow::Borrowed(&text[start..end]),
+        crate::Cow::Owned(text) => crate::Cow::Owned(text[start..end].to_string()),
+    }
+}
+
+/// Splits an argument at the first equals sign that is not within another element, or returns the argument unmodified if there is no such equals sign.
+#[allow(clippy::type_complexity)]
+pub fn split_argument(
+    mut name: Vec<crate::Node>,
+) -> Result<(Vec<crate::Node>, Vec<crate::Node>), Vec<crate::Node>> {
+    let Some((index, offset)) = name
+        .iter()
+        .enumerate()
+        .find_map(|(index, node)| match node {
+            crate::Node::Text { value, .. } => value.find('=').map(|offset| (index, offset)),
+            _ => None,
+        })
+    else {
+        return Err(name);
+    };
+    let mut value = name.split_off(index);
+    if let crate::Node::Text {
+        end,
+        start,
+        value: text,
+    } = value.remove(0)
+    {
+        if offset > 0 {
+            name.push(crate::Node::Text {
+                end: start + offset,
+                start,
+                value: slice(&text, 0, offset),
+            });
+        }
+        if offset + 1 < text.len() {
+            value.insert(
+                0,
+                crate::Node::Text {
+                    end,
+                    start: start + offset + 1,
+                    value: slice(&text, offset + 1, text.len()),
+                },
+            );
+        }
+    }
+    Ok((name, value))
+}
+
+/// Evaluates the cases of `#switch`. A case without a value falls through to the next case with a value, the case `#default` is used if no case matches, and a last case without a value is also used as the default.
+fn switch<'a>(
+    value: &str,
+    cases: impl Iterator<Item = Vec<crate::Node<'a>>>,
+    expand: &mut Expand<'_, 'a>,
+) -> String {
+    let mut default = None;
+    let mut is_default_next = false;
+    let mut is_found = false;
+    let mut last_test = None;
+    for case in cases {
+        match split_argument(case) {
+            Ok((test, result)) => {
+                last_test = None;
+                if is_found {
+                    return expand(result).trim().to_string();
+                }
+                let test = expand(test);
+                let test = test.trim();
+                if loose_equals(test, value) {
+                    return expand(result).trim().to_string();
+                }
+                if is_default_next || test == "#default" {
+                    default = Some(result);
+                    is_default_next = false;
+                }
+            }
+            Err(test) => {
+                let test = expand(test).trim().to_string();
+                if loose_equals(&test, value) {
+                    is_found = true;
+                } else if test == "#default" {
+                    is_default_next = true;
+                }
+                last_test = Some(test);
+            }
+        }
+    }
+    match (last_test, default) {
+        (Some(test), _) => test,
+        (None, Some(default)) => expand(default).trim().to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Evaluates `#tag`, returning an extension tag with the given content and attributes, or a self-closing tag if there is no content.
+fn tag<'a>(
+    name: &str,
+    content: Option<String>,
+    attributes: impl Iterator<Item = Vec<crate::Node<'a>>>,
+    expand: &mut Expand<'_, 'a>,
+) -> String {
+    let mut tag = format!("<{}", name);
+    for attribute in attributes {
+        match split_argument(attribute) {
+            Ok((attribute_name, value)) => {
+                let value = expand(value);
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .or_else(|| {
+                        value
+                            .strip_prefix('\'')
+                            .and_then(|value| value.strip_suffix('\''))
+                    })
+                    .unwrap_or(value);
+                tag.push_str(&format!(
+                    " {}=\"{}\"",
+                    expand(attribute_name).trim(),
+                    value.replace('"', "&quot;")
+                ));
+            }
+            Err(attribute_name) => {
+                let attribute_name = expand(attribute_name);
+                if !attribute_name.trim().is_empty() {
+                    tag.push(' ');
+                    tag.push_str(attribute_name.trim());
+                }
+            }
+        }
+    }
+    match content {
+        None => tag.push_str(" />"),
+        Some(content) => tag.push_str(&format!(">{}</{}>", content, name)),
+    }
+    tag
+}
@@ -0,0 +1,272 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+use crate::Visit;
+
+/// Extension tags whose content is text to be displayed.
+const TEXT_TAG_NAMES: &[&str] = &["nowiki", "pre"];
+
+struct PlainText<'p> {
+    /// The number of line breaks to write before the next text, to separate it from the previous text.
+    line_breaks: usize,
+    output: String,
+
+    /// The source of the values of magic variables, which are left out if there is none.
+    page: Option<&'p dyn crate::PageContext>,
+}
+
+impl crate::Node<'_> {
+    /// Extracts the readable text of the node.
+    ///
+    /// See [`Output::to_plain_text`](crate::Output::to_plain_text) for what is included.
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        let mut plain_text = PlainText::new(None);
+        plain_text.visit_node(self);
+        plain_text.finish()
+    }
+
+    /// Extracts the readable text of the node, with magic variables replaced by their values.
+    ///
+    /// See [`Output::to_plain_text_with_page`](crate::Output::to_plain_text_with_page) for what is included.
+    #[must_use]
+    pub fn to_plain_text_with_page(&self, page: &dyn crate::PageContext) -> String {
+        let mut plain_text = PlainText::new(Some(page));
+        plain_text.visit_node(self);
+        plain_text.finish()
+    }
+}
+
+impl crate::Output<'_> {
+    /// Extracts the readable text of the nodes, such as for indexing the text for search.
+    ///
+    /// Templates, functions, parameters, magic variables, comments, categories, interlanguage links, images, magic words, redirects, tags and formatting codes are left out, except for the content of `nowiki` and `pre` extension tags. Links are replaced by the text they display, including the link prefix and link trail, with the pipe trick applied when the text after the pipe is empty, external links by their label, if any, and bare URLs by the URL. Character entities are decoded. Headings, list items, table captions and table cells are written on lines of their own, and paragraphs are separated by an empty line.
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        let mut plain_text = PlainText::new(None);
+        plain_text.visit_nodes(&self.nodes);
+        plain_text.finish()
+    }
+
+    /// Extracts the readable text of the nodes the same as [`to_plain_text`](Self::to_plain_text), except that magic variables, such as `{{PAGENAME}}`, are replaced by the values given by the page context. Variables the page context doesn't know are left out.
+    #[must_use]
+    pub fn to_plain_text_with_page(&self, page: &dyn crate::PageContext) -> String {
+        let mut plain_text = PlainText::new(Some(page));
+        plain_text.visit_nodes(&self.nodes);
+        plain_text.finish()
+    }
+}
+
+impl<'p> PlainText<'p> {
+    fn new(page: Option<&'p dyn crate::PageContext>) -> Self {
+        PlainText {
+            line_breaks: 0,
+            output: String::new(),
+            page,
+        }
+    }
+
+    /// Writes the content of a node that is displayed as a block of its own.
+    fn block(&mut self, write: impl FnOnce(&mut Self)) {
+        self.line_break();
+        write(self);
+        self.line_break();
+    }
+
+    fn finish(self) -> String {
+        self.output.trim_end().to_string()
+    }
+
+    fn line_break(&mut self) {
+        self.line_breaks = self.line_breaks.max(1);
+    }
+
+    fn paragraph_break(&mut self) {
+        self.line_breaks = 2;
+    }
+
+    fn push_str(&mut self, text: &str) {
+        let text = if self.line_breaks > 0 || self.output.is_empty() {
+            text.trim_start()
+        } else {
+            text
+        };
+        if text.is_empty() {
+            return;
+        }
+        if !self.output.is_empty() && self.line_breaks > 0 {
+            self.output.truncate(self.output.trim_end().len());
+            for _ in 0..self.line_breaks {
+                self.output.push('\n');
+            }
+        }
+        self.line_breaks = 0;
+        self.output.push_str(text);
+    }
+}
+
+impl<'a> Visit<'a> for PlainText<'_> {
+    fn visit_bold(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_bold_italic(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_category(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_character_entity(&mut self, node: &crate::Node<'a>) {
+        if let crate::Node::CharacterEntity { character, .. } = node {
+            self.push_str(character.encode_utf8(&mut [0; 4]));
+        }
+    }
+
+    fn visit_comment(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_definition_list(&mut self, node: &crate::Node<'a>) {
+        self.block(|plain_text| plain_text.visit_children(node));
+    }
+
+    fn visit_definition_list_item(&mut self, item: &crate::DefinitionListItem<'a>) {
+        self.block(|plain_text| plain_text.visit_nodes(&item.nodes));
+    }
+
+    fn visit_end_tag(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_external_link(&mut self, node: &crate::Node<'a>) {
+        match node {
+            crate::Node::ExternalLink {
+                bracketed: false,
+                url,
+                ..
+            } => self.push_str(url),
+            _ => self.visit_children(node),
+        }
+    }
+
+    fn visit_function(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_gallery(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_heading(&mut self, node: &crate::Node<'a>) {
+        self.paragraph_break();
+        self.visit_children(node);
+        self.line_break();
+    }
+
+    fn visit_horizontal_divider(&mut self, _node: &crate::Node<'a>) {
+        self.paragraph_break();
+    }
+
+    fn visit_image(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_italic(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_language_link(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_link(&mut self, node: &crate::Node<'a>) {
+        if let crate::Node::Link {
+            prefix,
+            target,
+            text,
+            trail,
+            ..
+        } = node
+        {
+            if let Some(prefix) = prefix {
+                self.push_str(&prefix.value);
+            }
+            if text.is_empty() {
+                self.push_str(crate::link::pipe_trick(target));
+            } else {
+                self.visit_nodes(text);
+            }
+            if let Some(trail) = trail {
+                self.push_str(&trail.value);
+            }
+        }
+    }
+
+    fn visit_list_item(&mut self, item: &crate::ListItem<'a>) {
+        self.block(|plain_text| plain_text.visit_nodes(&item.nodes));
+    }
+
+    fn visit_magic_link(&mut self, node: &crate::Node<'a>) {
+        if let crate::Node::MagicLink { raw_id, type_, .. } = node {
+            self.push_str(&format!("{} {}", type_.keyword(), raw_id));
+        }
+    }
+
+    fn visit_magic_word(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_ordered_list(&mut self, node: &crate::Node<'a>) {
+        self.block(|plain_text| plain_text.visit_children(node));
+    }
+
+    fn visit_paragraph_break(&mut self, _node: &crate::Node<'a>) {
+        self.paragraph_break();
+    }
+
+    fn visit_parameter(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_preformatted(&mut self, node: &crate::Node<'a>) {
+        self.block(|plain_text| plain_text.visit_children(node));
+    }
+
+    fn visit_redirect(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_start_tag(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_table(&mut self, node: &crate::Node<'a>) {
+        if let crate::Node::Table { captions, rows, .. } = node {
+            self.block(|plain_text| {
+                for caption in captions {
+                    plain_text.visit_table_caption(caption);
+                }
+                for row in rows {
+                    plain_text.visit_table_row(row);
+                }
+            });
+        }
+    }
+
+    fn visit_table_caption(&mut self, caption: &crate::TableCaption<'a>) {
+        self.block(|plain_text| plain_text.visit_nodes(&caption.content));
+    }
+
+    fn visit_table_cell(&mut self, cell: &crate::TableCell<'a>) {
+        self.block(|plain_text| plain_text.visit_nodes(&cell.content));
+    }
+
+    fn visit_table_row(&mut self, row: &crate::TableRow<'a>) {
+        for cell in &row.cells {
+            self.visit_table_cell(cell);
+        }
+    }
+
+    fn visit_tag(&mut self, node: &crate::Node<'a>) {
+        if let crate::Node::Tag {
+            name,
+            raw_content: Some(raw_content),
+            ..
+        } = node
+            && TEXT_TAG_NAMES.contains(&name.as_ref())
+        {
+            self.push_str(&raw_content.value);
+        }
+    }
+
+    fn visit_template(&mut self, _node: &crate::Node<'a>) {}
+
+    fn visit_text(&mut self, node: &crate::Node<'a>) {
+        if let crate::Node::Text { value, .. } = node {
+            self.push_str(value);
+        }
+    }
+
+    fn visit_variable(&mut self, node: &crate::Node<'a>) {
+        if let crate::Node::Variable { id, .. } = node
+            && let Some(value) = self.page.and_then(|page| page.variable(id))
+        {
+            self.push_str(&value);
+        }
+    }
+}
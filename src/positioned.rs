@@ -16,8 +16,11 @@ macro_rules! impl_positioned {
     };
 }
 
+impl_positioned!(Attribute);
+impl_positioned!(AttributeValue);
 impl_positioned!(DefinitionListItem);
 impl_positioned!(FunctionParameter);
+impl_positioned!(ImageOption);
 impl_positioned!(ListItem);
 impl_positioned!(Parameter);
 impl_positioned!(TableCaption);
@@ -36,11 +39,15 @@ impl crate::Positioned for crate::Node<'_> {
             crate::Node::EndTag { end, .. } => end,
             crate::Node::ExternalLink { end, .. } => end,
             crate::Node::Function { end, .. } => end,
+            crate::Node::Gallery { end, .. } => end,
             crate::Node::Heading { end, .. } => end,
             crate::Node::HorizontalDivider { end, .. } => end,
+            crate::Node::Ignored { end, .. } => end,
             crate::Node::Image { end, .. } => end,
             crate::Node::Italic { end, .. } => end,
+            crate::Node::LanguageLink { end, .. } => end,
             crate::Node::Link { end, .. } => end,
+            crate::Node::MagicLink { end, .. } => end,
             crate::Node::MagicWord { end, .. } => end,
             crate::Node::OrderedList { end, .. } => end,
             crate::Node::ParagraphBreak { end, .. } => end,
@@ -53,6 +60,7 @@ impl crate::Positioned for crate::Node<'_> {
             crate::Node::Template { end, .. } => end,
             crate::Node::Text { end, .. } => end,
             crate::Node::UnorderedList { end, .. } => end,
+            crate::Node::Variable { end, .. } => end,
         }
     }
 
@@ -67,11 +75,15 @@ impl crate::Positioned for crate::Node<'_> {
             crate::Node::EndTag { start, .. } => start,
             crate::Node::ExternalLink { start, .. } => start,
             crate::Node::Function { start, .. } => start,
+            crate::Node::Gallery { start, .. } => start,
             crate::Node::Heading { start, .. } => start,
             crate::Node::HorizontalDivider { start, .. } => start,
+            crate::Node::Ignored { start, .. } => start,
             crate::Node::Image { start, .. } => start,
             crate::Node::Italic { start, .. } => start,
+            crate::Node::LanguageLink { start, .. } => start,
             crate::Node::Link { start, .. } => start,
+            crate::Node::MagicLink { start, .. } => start,
             crate::Node::MagicWord { start, .. } => start,
             crate::Node::OrderedList { start, .. } => start,
             crate::Node::ParagraphBreak { start, .. } => start,
@@ -84,6 +96,7 @@ impl crate::Positioned for crate::Node<'_> {
             crate::Node::Template { start, .. } => start,
             crate::Node::Text { start, .. } => start,
             crate::Node::UnorderedList { start, .. } => start,
+            crate::Node::Variable { start, .. } => start,
         }
     }
 }
@@ -0,0 +1,225 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+use crate::{HashMap, Visit};
+
+impl crate::Output<'_> {
+    /// Collects the references of the page from its `<ref>` tags and the footnotes they make up, the way the Cite extension of Mediawiki does.
+    ///
+    /// The wiki text given must be the wiki text that was parsed. The content of references is taken from it.
+    ///
+    /// A `<ref>` tag with content and a name defines a footnote that can be used again by a `<ref>` tag with the same name and group and no content. A `<ref>` tag in a `<references>` tag defines the content of a footnote used by name in the text before it. The footnotes are numbered separately for each group, and numbering starts over after each `<references>` tag of the group.
+    ///
+    /// ```
+    /// use parse_wiki_text_2::Configuration;
+    ///
+    /// let wiki_text = "a<ref name=x>b</ref> c<ref>d</ref> e<ref name=x/>";
+    /// let output = Configuration::default().parse(wiki_text).unwrap();
+    /// let references = output.references(wiki_text);
+    /// let footnotes: Vec<_> = references
+    ///     .footnotes
+    ///     .iter()
+    ///     .map(|footnote| (footnote.number, footnote.content.as_deref()))
+    ///     .collect();
+    /// assert_eq!(footnotes, [(1, Some("b")), (2, Some("d"))]);
+    /// assert_eq!(references.references[2].footnote, Some(0));
+    /// ```
+    #[must_use]
+    pub fn references(&self, wiki_text: &str) -> crate::References {
+        let mut collector = ReferenceCollector {
+            footnote_counts: HashMap::new(),
+            list_group: None,
+            named_footnotes: HashMap::new(),
+            references: crate::References {
+                footnotes: vec![],
+                references: vec![],
+                warnings: vec![],
+            },
+            wiki_text,
+        };
+        collector.visit_nodes(&self.nodes);
+        let mut references = collector.references;
+        for (index, footnote) in references.footnotes.iter().enumerate() {
+            if footnote.content.is_none()
+                && let Some(reference) = references
+                    .references
+                    .iter()
+                    .find(|reference| reference.footnote == Some(index))
+            {
+                references.warnings.push(crate::Warning {
+                    end: reference.end,
+                    message: crate::WarningMessage::UndefinedReference,
+                    start: reference.start,
+                });
+            }
+        }
+        references.warnings.sort_by_key(|warning| warning.start);
+        references
+    }
+}
+
+/// Collects the references of a page.
+struct ReferenceCollector<'w> {
+    /// The number of footnotes of each group since the last `<references>` tag of the group.
+    footnote_counts: HashMap<String, usize>,
+
+    /// The group of the `<references>` tag being visited, if any.
+    list_group: Option<String>,
+
+    /// The index of the footnote of each group and name since the last `<references>` tag of the group.
+    named_footnotes: HashMap<(String, String), usize>,
+    references: crate::References,
+    wiki_text: &'w str,
+}
+
+impl ReferenceCollector<'_> {
+    /// Adds a footnote and returns its index.
+    fn add_footnote(&mut self, group: &str, name: Option<&str>, content: Option<String>) -> usize {
+        let count = self.footnote_counts.entry(group.to_string()).or_default();
+        *count += 1;
+        let index = self.references.footnotes.len();
+        self.references.footnotes.push(crate::Footnote {
+            content,
+            continuations: vec![],
+            group: group.to_string(),
+            name: name.map(str::to_string),
+            number: *count,
+        });
+        if let Some(name) = name {
+            self.named_footnotes
+                .insert((group.to_string(), name.to_string()), index);
+        }
+        index
+    }
+
+    /// Gives a footnote the content of a `<ref>` tag, warning if the footnote already has different content.
+    fn define(&mut self, index: usize, content: Option<&String>, start: usize, end: usize) {
+        let Some(content) = content else {
+            return;
+        };
+        match &self.references.footnotes[index].content {
+            None => self.references.footnotes[index].content = Some(content.clone()),
+            Some(existing) if existing != content => {
+                self.references.warnings.push(crate::Warning {
+                    end,
+                    message: crate::WarningMessage::ConflictingReference,
+                    start,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    fn reference(
+        &mut self,
+        attributes: &[crate::Attribute],
+        nodes: &[crate::Node],
+        start: usize,
+        end: usize,
+    ) {
+        let name = attribute(attributes, "name");
+        let follow = attribute(attributes, "follow");
+        let group = match &self.list_group {
+            None => attribute(attributes, "group").unwrap_or_default(),
+            Some(group) => group.clone(),
+        };
+        let content = match (nodes.first(), nodes.last()) {
+            (Some(first), Some(last)) => self
+                .wiki_text
+                .get(crate::Positioned::start(first)..crate::Positioned::end(last))
+                .map(str::trim)
+                .filter(|content| !content.is_empty())
+                .map(str::to_string),
+            _ => None,
+        };
+        let named_footnote = |name: &Option<String>| {
+            name.as_ref().and_then(|name| {
+                self.named_footnotes
+                    .get(&(group.clone(), name.clone()))
+                    .copied()
+            })
+        };
+        let footnote = if self.list_group.is_some() {
+            let footnote = named_footnote(&name);
+            match footnote {
+                None => self.references.warnings.push(crate::Warning {
+                    end,
+                    message: crate::WarningMessage::UnusedReference,
+                    start,
+                }),
+                Some(index) => self.define(index, content.as_ref(), start, end),
+            }
+            footnote
+        } else if follow.is_some() {
+            Some(match named_footnote(&follow) {
+                None => self.add_footnote(&group, None, content.clone()),
+                Some(index) => {
+                    if let Some(content) = &content {
+                        self.references.footnotes[index]
+                            .continuations
+                            .push(content.clone());
+                    }
+                    index
+                }
+            })
+        } else {
+            Some(match named_footnote(&name) {
+                None => self.add_footnote(&group, name.as_deref(), content.clone()),
+                Some(index) => {
+                    self.define(index, content.as_ref(), start, end);
+                    index
+                }
+            })
+        };
+        self.references.references.push(crate::Reference {
+            content,
+            end,
+            follow,
+            footnote,
+            group,
+            list_defined: self.list_group.is_some(),
+            name,
+            start,
+        });
+    }
+}
+
+impl<'a> Visit<'a> for ReferenceCollector<'_> {
+    fn visit_tag(&mut self, node: &crate::Node<'a>) {
+        if let crate::Node::Tag {
+            attributes,
+            end,
+            name,
+            nodes,
+            start,
+            ..
+        } = node
+        {
+            match name.as_ref() {
+                "ref" => self.reference(attributes, nodes, *start, *end),
+                "references" if self.list_group.is_none() => {
+                    let group = attribute(attributes, "group").unwrap_or_default();
+                    self.list_group = Some(group.clone());
+                    self.visit_nodes(nodes);
+                    self.list_group = None;
+                    self.footnote_counts.remove(&group);
+                    self.named_footnotes
+                        .retain(|(footnote_group, _), _| *footnote_group != group);
+                }
+                _ => self.visit_children(node),
+            }
+        }
+    }
+}
+
+/// Returns the value of the attribute with the given name, with whitespace around it removed, if it's not empty.
+fn attribute(attributes: &[crate::Attribute], name: &str) -> Option<String> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.name == name)
+        .and_then(|attribute| attribute.value.as_ref())
+        .map(|value| value.value.trim())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
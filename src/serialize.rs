@@ -0,0 +1,1113 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+use crate::{Cow, Positioned};
+
+/// The maximum length of code that is searched for several delimiters in a row.
+const MAX_DELIMITERS_LENGTH: usize = 256;
+
+/// A validator of a delimiter and the fallback written when the code is not accepted.
+type Delimiter<'c> = (&'c dyn Fn(&str) -> bool, &'c str);
+
+type Validator<'b> = Box<dyn Fn(&str) -> bool + 'b>;
+
+enum Part<'b, 'a> {
+    /// Code between the other parts, copied from the wiki text when accepted by the validator and otherwise replaced by the fallback.
+    Delimiter(Validator<'b>, Cow<'b, str>),
+
+    /// Nodes written one after another.
+    Nodes(&'b [crate::Node<'a>]),
+
+    /// Text written as is, which was parsed from the code between the given start and end positions.
+    Text(usize, usize, Cow<'b, str>),
+}
+
+struct Writer<'b> {
+    /// The markers of the list items enclosing the nodes being written.
+    list_markers: String,
+    output: String,
+    wiki_text: &'b str,
+}
+
+impl crate::Node<'_> {
+    /// Converts the node back to wiki text.
+    ///
+    /// The wiki text given must be the wiki text the node was parsed from. Code that isn't represented in the node, such as whitespace and the delimiters of templates, is copied from the wiki text as long as the positions of the node still refer to valid code, and is otherwise written in a canonical form. This means a node can be modified, or even created from scratch with all positions set to zero, and still be written as valid wiki text. A node that hasn't been modified gives back exactly the code it was parsed from.
+    #[must_use]
+    pub fn to_wikitext(&self, wiki_text: &str) -> String {
+        let mut writer = Writer::new(wiki_text);
+        writer.node(self);
+        writer.output
+    }
+}
+
+impl crate::Output<'_> {
+    /// Converts the nodes back to wiki text.
+    ///
+    /// The wiki text given must be the wiki text that was parsed. See [`Node::to_wikitext`](crate::Node::to_wikitext) for how modified nodes are written. An output that hasn't been modified gives back exactly the wiki text it was parsed from, which makes it possible to make targeted changes to a page without disturbing the rest of it.
+    #[must_use]
+    pub fn to_wikitext(&self, wiki_text: &str) -> String {
+        let mut writer = Writer::new(wiki_text);
+        writer.sequence(
+            0,
+            wiki_text.len(),
+            &[
+                delimiter(is_blank, ""),
+                Part::Nodes(&self.nodes),
+                delimiter(is_blank, ""),
+            ],
+        );
+        writer.output
+    }
+}
+
+impl<'b> Writer<'b> {
+    fn new(wiki_text: &'b str) -> Self {
+        Writer {
+            list_markers: String::new(),
+            output: String::new(),
+            wiki_text,
+        }
+    }
+
+    /// Writes the code between the start position and the end position if it's made of the given delimiters, or whitespace if no delimiters are given, otherwise the fallbacks of the delimiters.
+    fn delimiters(&mut self, start: usize, end: usize, delimiters: &[Delimiter]) {
+        if delimiters.is_empty() {
+            self.delimiters(start, end, &[(&is_blank, "")]);
+            return;
+        }
+        let Some(text) = self.wiki_text.get(start..end) else {
+            for (_, fallback) in delimiters {
+                self.output.push_str(fallback);
+            }
+            return;
+        };
+        // When something between the positions has been removed, the code right before the end position is kept if valid.
+        let suffix_start = (0..=text.len())
+            .skip(text.len().saturating_sub(MAX_DELIMITERS_LENGTH))
+            .find(|&position| {
+                (position == 0 || text.is_char_boundary(position))
+                    && is_sequence_of(&text[position..], delimiters)
+            });
+        match suffix_start {
+            Some(position) => self.output.push_str(&text[position..]),
+            None => {
+                for (_, fallback) in delimiters {
+                    self.output.push_str(fallback);
+                }
+            }
+        }
+    }
+
+    /// Writes the items of a list, each starting with the given marker.
+    fn list<'c, 'a: 'c>(
+        &mut self,
+        start: usize,
+        end: usize,
+        list_marker: char,
+        items: impl Iterator<Item = (char, &'c [crate::Node<'a>])>,
+    ) {
+        let parent_list_markers_length = self.list_markers.len();
+        let mut parts = vec![];
+        for (index, (item_marker, nodes)) in items.enumerate() {
+            let fallback = if index == 0 {
+                item_marker.to_string()
+            } else {
+                format!("\n{}{}", self.list_markers, item_marker)
+            };
+            parts.push(delimiter(is_blank_or_list_markers, fallback));
+            parts.push(Part::Nodes(nodes));
+        }
+        self.list_markers.push(list_marker);
+        self.sequence(start, end, &parts);
+        self.list_markers.truncate(parent_list_markers_length);
+    }
+
+    fn node(&mut self, node: &crate::Node) {
+        match node {
+            crate::Node::Bold { .. } => self.output.push_str("'''"),
+            crate::Node::BoldItalic { .. } => self.output.push_str("'''''"),
+            crate::Node::Category {
+                end,
+                ordinal,
+                start,
+                target,
+            } => {
+                let has_pipe = !ordinal.is_empty();
+                self.sequence(
+                    *start,
+                    *end,
+                    &[
+                        delimiter(
+                            |text| is_link_start(text, target, has_pipe),
+                            link_start(target, has_pipe),
+                        ),
+                        Part::Nodes(ordinal),
+                        delimiter(is_link_end, "]]"),
+                    ],
+                );
+            }
+            crate::Node::CharacterEntity {
+                character,
+                end,
+                start,
+            } => match self.wiki_text.get(*start..*end) {
+                Some(text)
+                    if crate::character_entity::decode_character_entity(text)
+                        == Some(*character) =>
+                {
+                    self.output.push_str(text)
+                }
+                _ if character.is_ascii_punctuation() || character.is_whitespace() => self
+                    .output
+                    .push_str(&format!("&#{};", u32::from(*character))),
+                _ => self.output.push(*character),
+            },
+            crate::Node::Comment { end, start } => {
+                self.source(*start, *end, |text| text.starts_with("<!--"), "<!---->")
+            }
+            crate::Node::Ignored { end, start } => self.source(*start, *end, |_| true, ""),
+            crate::Node::DefinitionList { end, items, start } => self.list(
+                *start,
+                *end,
+                ':',
+                items.iter().map(|item| {
+                    (
+                        match item.type_ {
+                            crate::DefinitionListItemType::Details => ':',
+                            crate::DefinitionListItemType::Term => ';',
+                        },
+                        &item.nodes[..],
+                    )
+                }),
+            ),
+            crate::Node::EndTag { end, name, start } => self.source(
+                *start,
+                *end,
+                |text| is_end_tag(text, name),
+                &format!("</{}>", name),
+            ),
+            crate::Node::ExternalLink {
+                bracketed: false,
+                end,
+                start,
+                url,
+                ..
+            } => self.source(*start, *end, |text| text == url, url),
+            crate::Node::ExternalLink {
+                end,
+                label,
+                start,
+                url,
+                ..
+            } => {
+                let has_label = !label.is_empty();
+                self.sequence(
+                    *start,
+                    *end,
+                    &[
+                        delimiter(
+                            |text| is_external_link_start(text, url),
+                            if has_label {
+                                format!("[{} ", url)
+                            } else {
+                                format!("[{}", url)
+                            },
+                        ),
+                        Part::Nodes(label),
+                        delimiter(|text| text == "]", "]"),
+                    ],
+                );
+            }
+            crate::Node::Function {
+                end,
+                name,
+                parameters,
+                start,
+            } => {
+                let mut parts = vec![delimiter(is_template_start, "{{"), Part::Nodes(name)];
+                for (index, parameter) in parameters.iter().enumerate() {
+                    parts.push(if index == 0 {
+                        delimiter(is_function_name_end, ":")
+                    } else {
+                        delimiter(is_separator, "|")
+                    });
+                    parts.push(Part::Nodes(&parameter.value));
+                }
+                parts.push(delimiter(is_template_end, "}}"));
+                self.sequence(*start, *end, &parts);
+            }
+            crate::Node::Gallery {
+                attributes,
+                end,
+                entries,
+                name,
+                start,
+            } => {
+                let mut parts = vec![];
+                push_start_tag(&mut parts, name, attributes);
+                let is_self_closing = entries.is_empty()
+                    && self
+                        .wiki_text
+                        .get(*start..*end)
+                        .is_some_and(|text| text.ends_with("/>"));
+                if is_self_closing {
+                    parts.push(delimiter(is_start_tag_end, "/>"));
+                } else {
+                    parts.push(delimiter(is_start_tag_end, ">"));
+                    for entry in entries {
+                        parts.push(delimiter(is_gallery_gap, "\n"));
+                        // Comments in the target are left out of it, so the code is kept when the target is unchanged.
+                        let target = self.wiki_text.get(entry.start..entry.end).and_then(|code| {
+                            gallery_target_length(code, &entry.target).map(|length| {
+                                (entry.start + length, Cow::Borrowed(&code[..length]))
+                            })
+                        });
+                        let (target_end, target) =
+                            target.unwrap_or((entry.start, Cow::Borrowed(&entry.target)));
+                        parts.push(Part::Text(entry.start, target_end, target));
+                        for option in &entry.options {
+                            parts.push(delimiter(is_gallery_separator, "|"));
+                            push_image_option(&mut parts, option);
+                        }
+                    }
+                    parts.push(delimiter(is_gallery_gap, "\n"));
+                    parts.push(delimiter(
+                        |text| is_end_tag(text, name),
+                        format!("</{}>", name),
+                    ));
+                }
+                self.sequence(*start, *end, &parts);
+            }
+            crate::Node::Heading {
+                end,
+                level,
+                nodes,
+                start,
+            } => {
+                let equals_signs = "=".repeat(*level as usize);
+                self.sequence(
+                    *start,
+                    *end,
+                    &[
+                        delimiter(
+                            |text| text.trim_end() == equals_signs,
+                            equals_signs.as_str(),
+                        ),
+                        Part::Nodes(nodes),
+                        delimiter(
+                            |text| text.trim_start() == equals_signs,
+                            equals_signs.as_str(),
+                        ),
+                    ],
+                );
+            }
+            crate::Node::HorizontalDivider { end, start } => self.source(
+                *start,
+                *end,
+                |text| text.len() >= 4 && text.bytes().all(|character| character == b'-'),
+                "----",
+            ),
+            crate::Node::Image {
+                end,
+                options,
+                start,
+                target,
+            } => {
+                let has_pipe = !options.is_empty();
+                let mut parts = vec![delimiter(
+                    |text| is_link_start(text, target, has_pipe),
+                    link_start(target, has_pipe),
+                )];
+                for (index, option) in options.iter().enumerate() {
+                    if index > 0 {
+                        parts.push(delimiter(is_separator, "|"));
+                    }
+                    push_image_option(&mut parts, option);
+                }
+                parts.push(delimiter(is_link_end, "]]"));
+                self.sequence(*start, *end, &parts);
+            }
+            crate::Node::Italic { .. } => self.output.push_str("''"),
+            crate::Node::LanguageLink {
+                end,
+                start,
+                target,
+                text,
+            } => {
+                let has_pipe = !text.is_empty();
+                self.sequence(
+                    *start,
+                    *end,
+                    &[
+                        delimiter(
+                            |text| is_link_start(text, target, has_pipe),
+                            link_start(target, has_pipe),
+                        ),
+                        Part::Nodes(text),
+                        delimiter(is_link_end, "]]"),
+                    ],
+                );
+            }
+            crate::Node::Link {
+                end,
+                prefix,
+                reparsed,
+                start,
+                target,
+                text,
+                trail,
+            } => {
+                let mut parts = vec![];
+                if let Some(prefix) = prefix {
+                    parts.push(affix(prefix));
+                }
+                if *reparsed {
+                    parts.push(delimiter(
+                        |text| {
+                            text.strip_prefix("[[")
+                                .and_then(|text| text.strip_suffix("]]"))
+                                .is_some_and(|text| text.trim() == target)
+                        },
+                        format!("[[{}]]", target),
+                    ));
+                } else {
+                    // Without a pipe, the text is the target itself.
+                    let has_pipe = !matches!(&text[..], [crate::Node::Text { value, .. }] if value.trim() == target.trim());
+                    parts.push(delimiter(
+                        move |code| {
+                            if has_pipe {
+                                is_link_start(code, target, true)
+                            } else {
+                                code.trim_end() == "[["
+                            }
+                        },
+                        if has_pipe {
+                            link_start(target, true)
+                        } else {
+                            "[[".into()
+                        },
+                    ));
+                    parts.push(Part::Nodes(text));
+                    parts.push(delimiter(is_link_end, "]]"));
+                }
+                if let Some(trail) = trail {
+                    parts.push(affix(trail));
+                }
+                self.sequence(*start, *end, &parts);
+            }
+            crate::Node::MagicLink {
+                end,
+                id,
+                raw_id,
+                start,
+                type_,
+            } => {
+                // The identifier as written is kept as long as it still gives the normalized identifier.
+                let raw_id = if crate::magic_link::normalize(raw_id) == *id {
+                    raw_id
+                } else {
+                    id
+                };
+                self.source(
+                    *start,
+                    *end,
+                    |text| crate::magic_link::is_magic_link(text, *type_, raw_id),
+                    &format!("{} {}", type_.keyword(), raw_id),
+                )
+            }
+            crate::Node::MagicWord { end, id, start } => self.source(
+                *start,
+                *end,
+                |text| crate::magic_word::is_magic_word(text, id),
+                &format!("__{}__", id),
+            ),
+            crate::Node::OrderedList { end, items, start } => self.list(
+                *start,
+                *end,
+                '#',
+                items.iter().map(|item| ('#', &item.nodes[..])),
+            ),
+            crate::Node::ParagraphBreak { end, start } => self.source(
+                *start,
+                *end,
+                |text| is_blank(text) && text.contains('\n'),
+                "\n\n",
+            ),
+            crate::Node::Parameter {
+                default,
+                end,
+                name,
+                start,
+            } => {
+                let mut parts = vec![
+                    delimiter(|text| text.trim_end() == "{{{", "{{{"),
+                    Part::Nodes(name),
+                ];
+                if let Some(default) = default {
+                    parts.push(delimiter(is_separator, "|"));
+                    parts.push(Part::Nodes(default));
+                    // Mediawiki ignores any text after a second `|`.
+                    parts.push(delimiter(
+                        |text| {
+                            text.trim_start() == "}}}"
+                                || text.starts_with('|') && text.ends_with("}}}")
+                        },
+                        "}}}",
+                    ));
+                } else {
+                    parts.push(delimiter(|text| text.trim_start() == "}}}", "}}}"));
+                }
+                self.sequence(*start, *end, &parts);
+            }
+            crate::Node::Preformatted { end, nodes, .. } => {
+                // The space starting the first line is before the start of the node when the line starts with more than one space, so it's written together with the preceding code.
+                let start = start_of(node);
+                let wiki_text = self.wiki_text;
+                self.sequence(
+                    start,
+                    *end,
+                    &[
+                        delimiter(
+                            |text| {
+                                text.is_empty()
+                                    && start > 0
+                                    && wiki_text.as_bytes()[start - 1] == b' '
+                            },
+                            " ",
+                        ),
+                        Part::Nodes(nodes),
+                    ],
+                );
+            }
+            crate::Node::Redirect { end, start, target } => self.source(
+                *start,
+                *end,
+                |text| {
+                    text.starts_with('#')
+                        && text.ends_with("]]")
+                        && text.contains("[[")
+                        && text[text.find("[[").unwrap() + 2..text.len() - 2]
+                            .split('|')
+                            .next()
+                            .is_some_and(|text| text.trim() == target.trim())
+                },
+                &format!("#REDIRECT [[{}]]", target),
+            ),
+            crate::Node::StartTag {
+                attributes,
+                end,
+                name,
+                start,
+            } => {
+                let mut parts = vec![];
+                push_start_tag(&mut parts, name, attributes);
+                parts.push(delimiter(is_start_tag_end, ">"));
+                self.sequence(*start, *end, &parts);
+            }
+            crate::Node::Table { .. } => self.table(node, &[]),
+            crate::Node::Tag {
+                attributes,
+                end,
+                name,
+                nodes,
+                raw_content,
+                start,
+            } => {
+                let mut parts = vec![];
+                push_start_tag(&mut parts, name, attributes);
+                let is_self_closing = nodes.is_empty()
+                    && raw_content.is_none()
+                    && self
+                        .wiki_text
+                        .get(*start..*end)
+                        .is_some_and(|text| text.ends_with("/>"));
+                if is_self_closing {
+                    parts.push(delimiter(is_start_tag_end, "/>"));
+                } else {
+                    parts.push(delimiter(is_start_tag_end, ">"));
+                    parts.push(Part::Nodes(nodes));
+                    if let Some(raw_content) = raw_content {
+                        parts.push(Part::Text(
+                            raw_content.start,
+                            raw_content.end,
+                            Cow::Borrowed(&raw_content.value),
+                        ));
+                    }
+                    parts.push(delimiter(
+                        |text| is_end_tag(text, name),
+                        format!("</{}>", name),
+                    ));
+                }
+                self.sequence(*start, *end, &parts);
+            }
+            crate::Node::Template {
+                end,
+                name,
+                parameters,
+                start,
+            } => {
+                let mut parts = vec![delimiter(is_template_start, "{{"), Part::Nodes(name)];
+                for parameter in parameters {
+                    parts.push(delimiter(is_separator, "|"));
+                    if let Some(name) = &parameter.name {
+                        parts.push(Part::Nodes(name));
+                        parts.push(delimiter(|text| text.trim() == "=", "="));
+                    }
+                    parts.push(Part::Nodes(&parameter.value));
+                }
+                parts.push(delimiter(is_template_end, "}}"));
+                self.sequence(*start, *end, &parts);
+            }
+            crate::Node::Text { value, .. } => self.output.push_str(value),
+            crate::Node::UnorderedList { end, items, start } => self.list(
+                *start,
+                *end,
+                '*',
+                items.iter().map(|item| ('*', &item.nodes[..])),
+            ),
+            crate::Node::Variable {
+                end, name, start, ..
+            } => self.source(
+                *start,
+                *end,
+                |text| {
+                    text.strip_prefix("{{")
+                        .and_then(|text| text.strip_suffix("}}"))
+                        .is_some_and(|text| text.trim() == name)
+                },
+                &format!("{{{{{}}}}}", name),
+            ),
+        }
+    }
+
+    /// Writes nodes one after another, separated by the whitespace between them in the wiki text.
+    fn nodes(&mut self, nodes: &[crate::Node]) {
+        // Text within a table that isn't in a cell is moved in front of the table by the parser.
+        let mut is_stray = vec![false; nodes.len()];
+        let mut table_span = None;
+        for (index, node) in nodes.iter().enumerate().rev() {
+            match (node, table_span) {
+                (crate::Node::Table { end, start, .. }, _) => table_span = Some((*start, *end)),
+                (_, Some((table_start, table_end)))
+                    if node.start() >= table_start && node.end() <= table_end =>
+                {
+                    is_stray[index] = true
+                }
+                _ => table_span = None,
+            }
+        }
+        let mut previous_node: Option<&crate::Node> = None;
+        let mut stray_start_index = None;
+        for (index, node) in nodes.iter().enumerate() {
+            if is_stray[index] {
+                stray_start_index.get_or_insert(index);
+                continue;
+            }
+            if let Some(previous_node) = previous_node {
+                let fallback = if is_block(node)
+                    || is_block(previous_node)
+                        && !matches!(previous_node, crate::Node::HorizontalDivider { .. })
+                {
+                    format!("\n{}", self.list_markers)
+                } else {
+                    String::new()
+                };
+                self.delimiters(
+                    previous_node.end(),
+                    start_of(node),
+                    &[(&is_blank_or_list_markers, &fallback)],
+                );
+            }
+            match stray_start_index.take() {
+                None => self.node(node),
+                Some(stray_start_index) => self.table(node, &nodes[stray_start_index..index]),
+            }
+            previous_node = Some(node);
+        }
+    }
+
+    /// Writes a table, with the nodes that the parser moved out of the table written in their original place within the table.
+    fn table(&mut self, table: &crate::Node, stray_nodes: &[crate::Node]) {
+        let crate::Node::Table {
+            attributes,
+            captions,
+            end,
+            rows,
+            start,
+        } = table
+        else {
+            unreachable!();
+        };
+        let mut parts = vec![delimiter(is_table_syntax, "{|")];
+        push_table_attributes(&mut parts, attributes);
+        let mut captions = captions.iter().peekable();
+        for row in rows {
+            while let Some(caption) = captions.next_if(|caption| caption.start < row.start) {
+                push_table_element(&mut parts, "\n|+", &caption.attributes, &caption.content);
+            }
+            parts.push(delimiter(is_table_syntax, "\n|-"));
+            push_table_attributes(&mut parts, &row.attributes);
+            for cell in &row.cells {
+                push_table_element(
+                    &mut parts,
+                    match cell.type_ {
+                        crate::TableCellType::Heading => "\n!",
+                        crate::TableCellType::Ordinary => "\n|",
+                    },
+                    &cell.attributes,
+                    &cell.content,
+                );
+            }
+        }
+        for caption in captions {
+            push_table_element(&mut parts, "\n|+", &caption.attributes, &caption.content);
+        }
+        parts.push(delimiter(is_table_syntax, "\n|}"));
+        for stray_node in stray_nodes {
+            let index = parts
+                .iter()
+                .position(|part| match part {
+                    Part::Nodes([first_node, ..]) => start_of(first_node) > stray_node.start(),
+                    Part::Text(start, ..) => *start > stray_node.start(),
+                    _ => false,
+                })
+                .unwrap_or(parts.len() - 1);
+            parts.insert(index, Part::Nodes(std::slice::from_ref(stray_node)));
+        }
+        self.sequence(*start, *end, &parts);
+    }
+
+    /// Writes the nodes and delimiters, starting at the given start position and ending at the given end position in the wiki text.
+    fn sequence(&mut self, start: usize, end: usize, parts: &[Part]) {
+        let mut position = start;
+        let mut delimiters: Vec<Delimiter> = vec![];
+        for part in parts {
+            match part {
+                Part::Delimiter(is_valid, fallback) => delimiters.push((is_valid, fallback)),
+                Part::Nodes(nodes) => {
+                    if let Some(last_node) = nodes.last() {
+                        self.delimiters(position, start_of_nodes(nodes), &delimiters);
+                        delimiters.clear();
+                        self.nodes(nodes);
+                        position = last_node.end();
+                    }
+                }
+                Part::Text(text_start, text_end, text) => {
+                    self.delimiters(position, *text_start, &delimiters);
+                    delimiters.clear();
+                    self.output.push_str(text);
+                    position = *text_end;
+                }
+            }
+        }
+        self.delimiters(position, end, &delimiters);
+    }
+
+    /// Writes the code between the start position and the end position if accepted by the validator, otherwise the fallback.
+    fn source(
+        &mut self,
+        start: usize,
+        end: usize,
+        is_valid: impl Fn(&str) -> bool,
+        fallback: &str,
+    ) {
+        self.delimiters(start, end, &[(&is_valid, fallback)]);
+    }
+}
+
+/// Returns the part for a link prefix or link trail.
+fn affix<'b, 'a>(affix: &'b crate::LinkAffix) -> Part<'b, 'a> {
+    Part::Text(affix.start, affix.end, Cow::Borrowed(&affix.value))
+}
+
+fn delimiter<'b, 'a>(
+    is_valid: impl Fn(&str) -> bool + 'b,
+    fallback: impl Into<Cow<'b, str>>,
+) -> Part<'b, 'a> {
+    Part::Delimiter(Box::new(is_valid), fallback.into())
+}
+
+/// Returns the length of the code at the start of the text that is the target of an image in a gallery with comments in it, or `None` if the code doesn't give the target.
+fn gallery_target_length(text: &str, target: &str) -> Option<usize> {
+    let mut position = 0;
+    let mut target = target;
+    loop {
+        let code = &text[position..];
+        if let Some(comment) = code.strip_prefix("<!--") {
+            position += 4 + comment.find("-->")? + 3;
+            continue;
+        }
+        let Some(character) = target.chars().next() else {
+            return Some(position);
+        };
+        if !code.starts_with(character) {
+            return None;
+        }
+        position += character.len_utf8();
+        target = &target[character.len_utf8()..];
+    }
+}
+
+/// Returns the keyword written before and after the value of an image option of the given type when the option has no valid position.
+fn image_option_keyword(
+    type_: crate::ImageOptionType,
+    value: &[crate::Node],
+) -> (&'static str, &'static str) {
+    match type_ {
+        crate::ImageOptionType::Alignment(crate::ImageAlignment::Center) => ("center", ""),
+        crate::ImageOptionType::Alignment(crate::ImageAlignment::Left) => ("left", ""),
+        crate::ImageOptionType::Alignment(crate::ImageAlignment::None) => ("none", ""),
+        crate::ImageOptionType::Alignment(crate::ImageAlignment::Right) => ("right", ""),
+        crate::ImageOptionType::Alt => ("alt=", ""),
+        crate::ImageOptionType::Border => ("border", ""),
+        crate::ImageOptionType::Caption | crate::ImageOptionType::Unrecognized => ("", ""),
+        crate::ImageOptionType::Class => ("class=", ""),
+        crate::ImageOptionType::Format(crate::ImageFormat::Frame) => ("frame", ""),
+        crate::ImageOptionType::Format(crate::ImageFormat::Frameless) => ("frameless", ""),
+        crate::ImageOptionType::Format(crate::ImageFormat::ManualThumbnail) => ("thumb=", ""),
+        crate::ImageOptionType::Format(crate::ImageFormat::Thumbnail) => ("thumb", ""),
+        crate::ImageOptionType::Lang => ("lang=", ""),
+        crate::ImageOptionType::Link => ("link=", ""),
+        crate::ImageOptionType::Page => ("page=", ""),
+        crate::ImageOptionType::Size => ("", "px"),
+        crate::ImageOptionType::Upright if value.is_empty() => ("upright", ""),
+        crate::ImageOptionType::Upright => ("upright=", ""),
+        crate::ImageOptionType::VerticalAlignment(alignment) => (
+            match alignment {
+                crate::ImageVerticalAlignment::Baseline => "baseline",
+                crate::ImageVerticalAlignment::Bottom => "bottom",
+                crate::ImageVerticalAlignment::Middle => "middle",
+                crate::ImageVerticalAlignment::Sub => "sub",
+                crate::ImageVerticalAlignment::Super => "super",
+                crate::ImageVerticalAlignment::TextBottom => "text-bottom",
+                crate::ImageVerticalAlignment::TextTop => "text-top",
+                crate::ImageVerticalAlignment::Top => "top",
+            },
+            "",
+        ),
+    }
+}
+
+fn is_blank(text: &str) -> bool {
+    text.bytes()
+        .all(|character| matches!(character, b'\t' | b'\n' | b' '))
+}
+
+fn is_blank_or_list_markers(text: &str) -> bool {
+    text.bytes()
+        .all(|character| matches!(character, b'\t' | b'\n' | b' ' | b'#' | b'*' | b':' | b';'))
+}
+
+/// Nodes that must start on a line of their own, and except for horizontal dividers, end their line.
+fn is_block(node: &crate::Node) -> bool {
+    matches!(
+        node,
+        crate::Node::DefinitionList { .. }
+            | crate::Node::Heading { .. }
+            | crate::Node::HorizontalDivider { .. }
+            | crate::Node::OrderedList { .. }
+            | crate::Node::Preformatted { .. }
+            | crate::Node::Table { .. }
+            | crate::Node::UnorderedList { .. }
+    )
+}
+
+/// Checks that the code is an end tag with the given name, possibly followed by ignored attributes.
+fn is_end_tag(text: &str, name: &str) -> bool {
+    text.strip_prefix("</")
+        .and_then(|text| text.strip_suffix('>'))
+        .and_then(|text| Some((text.get(..name.len())?, text.get(name.len()..)?)))
+        .is_some_and(|(tag_name, rest)| {
+            tag_name.eq_ignore_ascii_case(name)
+                && (rest.is_empty()
+                    || rest.starts_with(|character: char| character.is_ascii_whitespace()))
+        })
+}
+
+fn is_function_name_end(text: &str) -> bool {
+    text.trim() == ":"
+}
+
+/// Checks that the code is `[`, the URL and whitespace, if any.
+fn is_external_link_start(text: &str, url: &str) -> bool {
+    text.strip_prefix('[')
+        .and_then(|text| text.strip_prefix(url))
+        .is_some_and(|text| {
+            text.chars()
+                .all(|character| character != '\n' && character.is_whitespace())
+        })
+}
+
+/// Checks that the code is made of comments and characters accepted by the predicate.
+fn is_comments_and(text: &str, is_valid: impl Fn(char) -> bool) -> bool {
+    let mut text = text.trim_start_matches(&is_valid);
+    while let Some(rest) = text.strip_prefix("<!--") {
+        match rest.find("-->") {
+            None => return false,
+            Some(position) => text = rest[position + 3..].trim_start_matches(&is_valid),
+        }
+    }
+    text.is_empty()
+}
+
+/// Checks that the code between the lines of a gallery is made of whitespace and comments.
+fn is_gallery_gap(text: &str) -> bool {
+    is_comments_and(text, char::is_whitespace)
+}
+
+/// Checks that the code between the target and the options of an image in a gallery is `|` with whitespace and comments on either side.
+fn is_gallery_separator(text: &str) -> bool {
+    text.match_indices('|').any(|(position, _)| {
+        is_gallery_gap(&text[..position]) && is_gallery_gap(&text[position + 1..])
+    })
+}
+
+fn is_image_option_keyword(text: &str) -> bool {
+    !text.is_empty() && !text.contains(['\n', '[', ']', '{', '|', '}'])
+}
+
+fn is_link_end(text: &str) -> bool {
+    text.trim_start() == "]]"
+}
+
+/// Checks that the code is `[[`, the target and, if there is text after it, `|`.
+fn is_link_start(text: &str, target: &str, has_pipe: bool) -> bool {
+    let Some(text) = text.strip_prefix("[[") else {
+        return false;
+    };
+    let text = text.trim_end();
+    match text.strip_suffix('|') {
+        None => !has_pipe && text.trim() == target.trim(),
+        Some(text) => text.trim() == target.trim(),
+    }
+}
+
+/// Checks that the text is made of the given delimiters, one after another, as accepted by their validators.
+fn is_sequence_of(text: &str, delimiters: &[Delimiter]) -> bool {
+    if let [(is_valid, _)] = delimiters {
+        return is_valid(text);
+    }
+    if text.len() > MAX_DELIMITERS_LENGTH {
+        return false;
+    }
+    let boundaries: Vec<usize> = (0..=text.len())
+        .filter(|&position| text.is_char_boundary(position))
+        .collect();
+    let mut positions = vec![0];
+    for (is_valid, _) in delimiters {
+        let mut next_positions = vec![];
+        for &boundary in &boundaries {
+            if positions
+                .iter()
+                .any(|&position| position <= boundary && is_valid(&text[position..boundary]))
+            {
+                next_positions.push(boundary);
+            }
+        }
+        positions = next_positions;
+    }
+    positions.last() == Some(&text.len())
+}
+
+fn is_separator(text: &str) -> bool {
+    text.trim() == "|"
+}
+
+fn is_start_tag_end(text: &str) -> bool {
+    text.strip_suffix('>').is_some_and(|text| {
+        text.bytes()
+            .all(|character| character.is_ascii_whitespace() || matches!(character, b'/' | b'='))
+    })
+}
+
+fn is_table_syntax(text: &str) -> bool {
+    text.bytes().all(|character| {
+        matches!(
+            character,
+            b'\t' | b'\n' | b' ' | b'!' | b'+' | b'-' | b'{' | b'|' | b'}'
+        )
+    })
+}
+
+/// Checks that the code around the HTML attributes of a table is made of what the parser skips there: whitespace, comments and stray characters.
+fn is_table_attribute_gap(text: &str) -> bool {
+    is_comments_and(text, |character| {
+        character.is_ascii_whitespace() || matches!(character, '/' | '=' | '>')
+    })
+}
+
+fn is_template_end(text: &str) -> bool {
+    text.trim_start() == "}}"
+}
+
+fn is_template_start(text: &str) -> bool {
+    text.trim_end() == "{{"
+}
+
+fn link_start(target: &str, has_pipe: bool) -> String {
+    if has_pipe {
+        format!("[[{}|", target)
+    } else {
+        format!("[[{}", target)
+    }
+}
+
+/// Pushes the parts for the value of an image option, with its keyword, if any.
+fn push_image_option<'b, 'a>(parts: &mut Vec<Part<'b, 'a>>, option: &'b crate::ImageOption<'a>) {
+    let (prefix, suffix) = image_option_keyword(option.type_, &option.value);
+    if !prefix.is_empty() {
+        parts.push(delimiter(is_image_option_keyword, prefix));
+    }
+    parts.push(Part::Nodes(&option.value));
+    if !suffix.is_empty() {
+        parts.push(delimiter(is_image_option_keyword, suffix));
+    }
+}
+
+fn push_start_tag<'b>(
+    parts: &mut Vec<Part<'b, '_>>,
+    name: &'b str,
+    attributes: &'b [crate::Attribute],
+) {
+    parts.push(delimiter(
+        move |text| {
+            text.strip_prefix('<')
+                .is_some_and(|text| text.eq_ignore_ascii_case(name))
+        },
+        format!("<{}", name),
+    ));
+    for attribute in attributes {
+        let name = &attribute.name;
+        parts.push(delimiter(
+            move |text| {
+                // The parser skips a stray `=` or `>` where an attribute name was expected.
+                let attribute_name = text.trim_start_matches(|character: char| {
+                    character.is_ascii_whitespace() || matches!(character, '/' | '=' | '>')
+                });
+                attribute_name.eq_ignore_ascii_case(name)
+            },
+            format!(" {}", name),
+        ));
+        if let Some(value) = &attribute.value {
+            let quote = match value.quote {
+                crate::AttributeQuote::Double => "\"",
+                crate::AttributeQuote::None => "",
+                crate::AttributeQuote::Single => "'",
+            };
+            parts.push(delimiter(
+                move |text| {
+                    text.strip_suffix(quote)
+                        .is_some_and(|text| text.trim() == "=")
+                },
+                format!("={}", quote),
+            ));
+            parts.push(Part::Text(
+                value.start,
+                value.end,
+                match quote {
+                    "" => Cow::Borrowed(&value.value),
+                    _ if value.value.contains(quote) => Cow::Owned(
+                        value
+                            .value
+                            .replace(quote, &format!("&#{};", u32::from(quote.as_bytes()[0]))),
+                    ),
+                    _ => Cow::Borrowed(&value.value),
+                },
+            ));
+            // The closing quote is missing when the value is unterminated.
+            parts.push(delimiter(
+                move |text| text.is_empty() || text == quote,
+                quote,
+            ));
+        }
+    }
+}
+
+/// Pushes the parts for the HTML attributes of a table, table caption, table row or table cell, followed by the code after them that the parser skips.
+fn push_table_attributes<'b, 'a>(
+    parts: &mut Vec<Part<'b, 'a>>,
+    attributes: &'b [crate::TableAttribute<'a>],
+) {
+    for attribute in attributes {
+        parts.push(delimiter(is_table_attribute_gap, " "));
+        if attribute.name_nodes.is_empty() {
+            parts.push(Part::Text(
+                attribute.start,
+                match attribute.value {
+                    None => attribute.end,
+                    Some(_) => attribute.start + attribute.name.len(),
+                },
+                Cow::Borrowed(&attribute.name),
+            ));
+        } else {
+            parts.push(Part::Nodes(&attribute.name_nodes));
+        }
+        if let Some(value) = &attribute.value {
+            let is_unterminated = attribute.end > 0 && attribute.end == value.end;
+            let quote = match value.quote {
+                crate::AttributeQuote::Double => "\"",
+                crate::AttributeQuote::None => "",
+                crate::AttributeQuote::Single => "'",
+            };
+            parts.push(delimiter(
+                move |text| {
+                    text.strip_suffix(quote)
+                        .is_some_and(|text| text.trim() == "=")
+                },
+                format!("={}", quote),
+            ));
+            parts.push(Part::Nodes(&value.nodes));
+            // The closing quote is missing when the value is unterminated.
+            parts.push(delimiter(
+                move |text| (is_unterminated && text.is_empty()) || text == quote,
+                quote,
+            ));
+        }
+    }
+    parts.push(delimiter(is_table_attribute_gap, ""));
+}
+
+fn push_table_element<'b, 'a>(
+    parts: &mut Vec<Part<'b, 'a>>,
+    token: &'static str,
+    attributes: &'b Option<Vec<crate::TableAttribute<'a>>>,
+    content: &'b [crate::Node<'a>],
+) {
+    parts.push(delimiter(is_table_syntax, token));
+    if let Some(attributes) = attributes {
+        push_table_attributes(parts, attributes);
+        parts.push(delimiter(is_table_syntax, "|"));
+    }
+    parts.push(Part::Nodes(content));
+}
+
+/// The position in the wiki text where the code of a list of nodes starts, taking into account nodes moved out of a table by the parser.
+fn start_of_nodes(nodes: &[crate::Node]) -> usize {
+    let start = nodes.first().map_or(0, start_of);
+    for node in nodes {
+        if let crate::Node::Table {
+            end: table_end,
+            start: table_start,
+            ..
+        } = node
+        {
+            if *table_start <= start && nodes[0].end() <= *table_end {
+                return *table_start;
+            }
+            break;
+        }
+    }
+    start
+}
+
+/// The position in the wiki text where the code of a node starts.
+fn start_of(node: &crate::Node) -> usize {
+    match node {
+        crate::Node::Preformatted { nodes, start, .. } => nodes
+            .first()
+            .map_or(*start, |first_node| start_of(first_node).min(*start)),
+        _ => node.start(),
+    }
+}
@@ -12,7 +12,9 @@ pub enum OpenNodeType<'a> {
     DefinitionList {
         items: Vec<crate::DefinitionListItem<'a>>,
     },
-    ExternalLink,
+    ExternalLink {
+        url: &'a str,
+    },
     Function {
         name: Vec<crate::Node<'a>>,
         parameters: Vec<crate::FunctionParameter<'a>>,
@@ -35,6 +37,7 @@ pub enum OpenNodeType<'a> {
     Preformatted,
     Table(Table<'a>),
     Tag {
+        attributes: Vec<crate::Attribute<'a>>,
         name: crate::Cow<'a, str>,
     },
     Template {
@@ -56,10 +59,10 @@ pub struct State<'a> {
 }
 
 pub struct Table<'a> {
-    pub attributes: Vec<crate::Node<'a>>,
+    pub attributes: Vec<crate::TableAttribute<'a>>,
     pub before: Vec<crate::Node<'a>>,
     pub captions: Vec<crate::TableCaption<'a>>,
-    pub child_element_attributes: Option<Vec<crate::Node<'a>>>,
+    pub child_element_attributes: Option<Vec<crate::TableAttribute<'a>>>,
     pub rows: Vec<crate::TableRow<'a>>,
     pub start: usize,
     pub state: TableState,
@@ -153,7 +156,7 @@ pub fn flush<'a>(
         nodes.push(crate::Node::Text {
             end: end_position,
             start: flushed_position,
-            value: &wiki_text[flushed_position..end_position],
+            value: crate::Cow::Borrowed(&wiki_text[flushed_position..end_position]),
         });
     }
 }
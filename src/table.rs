@@ -198,14 +198,20 @@ fn change_state(
         }
         TableState::Row => {
             table.rows.push(crate::TableRow {
-                attributes: std::mem::take(&mut state.nodes),
+                attributes: crate::attribute::parse_table_attributes(
+                    state.wiki_text,
+                    std::mem::take(&mut state.nodes),
+                ),
                 cells: vec![],
                 end,
                 start: table.start,
             });
         }
         TableState::TableAttributes => {
-            table.attributes = std::mem::take(&mut state.nodes);
+            table.attributes = crate::attribute::parse_table_attributes(
+                state.wiki_text,
+                std::mem::take(&mut state.nodes),
+            );
         }
     }
     table.start = position_before_token;
@@ -294,14 +300,20 @@ fn parse_end(
                 }
                 TableState::Row => {
                     rows.push(crate::TableRow {
-                        attributes: std::mem::replace(&mut state.nodes, open_node.nodes),
+                        attributes: crate::attribute::parse_table_attributes(
+                            state.wiki_text,
+                            std::mem::replace(&mut state.nodes, open_node.nodes),
+                        ),
                         cells: vec![],
                         end: position_before_line_break,
                         start,
                     });
                 }
                 TableState::TableAttributes => {
-                    attributes = std::mem::replace(&mut state.nodes, open_node.nodes);
+                    attributes = crate::attribute::parse_table_attributes(
+                        state.wiki_text,
+                        std::mem::replace(&mut state.nodes, open_node.nodes),
+                    );
                 }
             }
             state.scan_position = position_after_token;
@@ -404,7 +416,10 @@ fn parse_line_break(
                     ),
                     state.wiki_text,
                 );
-                table.attributes = std::mem::take(&mut state.nodes);
+                table.attributes = crate::attribute::parse_table_attributes(
+                    state.wiki_text,
+                    std::mem::take(&mut state.nodes),
+                );
                 table.start = position_after_token;
                 table.state = TableState::Before;
                 state.flushed_position = position_after_token;
@@ -424,7 +439,10 @@ fn parse_line_break(
                     state.wiki_text,
                 );
                 table.rows.push(crate::TableRow {
-                    attributes: std::mem::take(&mut state.nodes),
+                    attributes: crate::attribute::parse_table_attributes(
+                        state.wiki_text,
+                        std::mem::take(&mut state.nodes),
+                    ),
                     cells: vec![],
                     end: position_before_line_break,
                     start: table.start,
@@ -572,7 +590,10 @@ pub fn parse_inline_token(state: &mut crate::State) {
                     crate::state::skip_whitespace_backwards(state.wiki_text, position_before_token),
                     state.wiki_text,
                 );
-                table.child_element_attributes = Some(std::mem::take(&mut state.nodes));
+                table.child_element_attributes = Some(crate::attribute::parse_table_attributes(
+                    state.wiki_text,
+                    std::mem::take(&mut state.nodes),
+                ));
                 state.scan_position = position_before_token + 1;
                 while let Some(character) = state.wiki_text.as_bytes().get(state.scan_position) {
                     match character {
@@ -31,7 +31,7 @@ pub fn parse_end_tag(state: &mut crate::State, configuration: &crate::Configurat
                 start: tag_name_start_position,
             });
         }
-        Some(crate::TagClass::ExtensionTag) => {
+        Some(crate::TagClass::ExtensionTag(content_model)) => {
             let mut tag_end_position = tag_name_end_position;
             loop {
                 match state.get_byte(tag_end_position) {
@@ -82,13 +82,35 @@ pub fn parse_end_tag(state: &mut crate::State, configuration: &crate::Configurat
                         tag_end_position += 1;
                         state.flushed_position = tag_end_position;
                         state.scan_position = state.flushed_position;
+                        let attributes = match open_node.type_ {
+                            crate::OpenNodeType::Tag { attributes, .. } => attributes,
+                            _ => unreachable!(),
+                        };
                         let nodes = std::mem::replace(&mut state.nodes, open_node.nodes);
-                        state.nodes.push(crate::Node::Tag {
-                            end: state.scan_position,
-                            name: tag_name,
-                            nodes,
-                            start: open_node.start,
-                        });
+                        let node = if *content_model == crate::TagContentModel::Gallery {
+                            crate::Node::Gallery {
+                                attributes,
+                                end: state.scan_position,
+                                entries: crate::gallery::parse_gallery(
+                                    state.wiki_text,
+                                    configuration,
+                                    nodes,
+                                    start_position,
+                                ),
+                                name: tag_name,
+                                start: open_node.start,
+                            }
+                        } else {
+                            crate::Node::Tag {
+                                attributes,
+                                end: state.scan_position,
+                                name: tag_name,
+                                nodes,
+                                raw_content: None,
+                                start: open_node.start,
+                            }
+                        };
+                        state.nodes.push(node);
                     }
                 }
             }
@@ -163,35 +185,59 @@ pub fn parse_start_tag(state: &mut crate::State, configuration: &crate::Configur
             }
             Some(tag_end_position) => {
                 let tag_end_position = tag_name_end_position + tag_end_position + 1;
+                let is_self_closing = tag_end_position - 2 >= tag_name_end_position
+                    && state.get_byte(tag_end_position - 2) == Some(b'/');
+                let attributes = crate::attribute::parse_attributes(
+                    state.wiki_text,
+                    tag_name_end_position,
+                    if is_self_closing {
+                        tag_end_position - 2
+                    } else {
+                        tag_end_position - 1
+                    },
+                );
                 match tag_class {
-                    crate::TagClass::ExtensionTag => {
-                        if state.get_byte(tag_end_position - 2) == Some(b'/') {
+                    crate::TagClass::ExtensionTag(content_model) => {
+                        if is_self_closing {
                             state.flush(start_position);
                             state.flushed_position = tag_end_position;
                             state.scan_position = state.flushed_position;
-                            state.nodes.push(crate::Node::Tag {
-                                end: tag_end_position,
-                                name: tag_name,
-                                nodes: vec![],
-                                start: start_position,
-                            });
+                            state.nodes.push(
+                                if *content_model == crate::TagContentModel::Gallery {
+                                    crate::Node::Gallery {
+                                        attributes,
+                                        end: tag_end_position,
+                                        entries: vec![],
+                                        name: tag_name,
+                                        start: start_position,
+                                    }
+                                } else {
+                                    crate::Node::Tag {
+                                        attributes,
+                                        end: tag_end_position,
+                                        name: tag_name,
+                                        nodes: vec![],
+                                        raw_content: None,
+                                        start: start_position,
+                                    }
+                                },
+                            );
+                        } else if *content_model == crate::TagContentModel::Raw {
+                            parse_raw_tag(
+                                state,
+                                start_position,
+                                tag_end_position,
+                                &tag_name,
+                                attributes,
+                            );
                         } else {
-                            match &tag_name as _ {
-                                "math" | "nowiki" => {
-                                    parse_plain_text_tag(
-                                        state,
-                                        start_position,
-                                        tag_end_position,
-                                        &tag_name,
-                                    );
-                                }
-                                _ => {
-                                    state.push_open_node(
-                                        crate::OpenNodeType::Tag { name: tag_name },
-                                        tag_end_position,
-                                    );
-                                }
-                            }
+                            state.push_open_node(
+                                crate::OpenNodeType::Tag {
+                                    attributes,
+                                    name: tag_name,
+                                },
+                                tag_end_position,
+                            );
                         }
                     }
                     crate::TagClass::Tag => {
@@ -199,6 +245,7 @@ pub fn parse_start_tag(state: &mut crate::State, configuration: &crate::Configur
                         state.flushed_position = tag_end_position;
                         state.scan_position = state.flushed_position;
                         state.nodes.push(crate::Node::StartTag {
+                            attributes,
                             end: tag_end_position,
                             name: tag_name,
                             start: start_position,
@@ -210,11 +257,12 @@ pub fn parse_start_tag(state: &mut crate::State, configuration: &crate::Configur
     }
 }
 
-fn parse_plain_text_tag(
-    state: &mut crate::State<'_>,
+fn parse_raw_tag<'a>(
+    state: &mut crate::State<'a>,
     position_before_start_tag: usize,
     position_after_start_tag: usize,
     start_tag_name: &str,
+    mut attributes: Vec<crate::Attribute<'a>>,
 ) {
     loop {
         match state.get_byte(state.scan_position) {
@@ -229,11 +277,12 @@ fn parse_plain_text_tag(
             }
             Some(b'<') => {
                 if state.get_byte(state.scan_position + 1) == Some(b'/')
-                    && parse_plain_text_end_tag(
+                    && parse_raw_end_tag(
                         state,
                         position_before_start_tag,
                         position_after_start_tag,
                         start_tag_name,
+                        &mut attributes,
                     )
                 {
                     break;
@@ -245,11 +294,12 @@ fn parse_plain_text_tag(
     }
 }
 
-fn parse_plain_text_end_tag(
-    state: &mut crate::State<'_>,
+fn parse_raw_end_tag<'a>(
+    state: &mut crate::State<'a>,
     position_before_start_tag: usize,
     position_after_start_tag: usize,
     start_tag_name: &str,
+    attributes: &mut Vec<crate::Attribute<'a>>,
 ) -> bool {
     let position_before_end_tag = state.scan_position;
     let position_before_end_tag_name = state.scan_position + 2;
@@ -276,21 +326,22 @@ fn parse_plain_text_end_tag(
         end_tag_name.to_ascii_lowercase().into()
     };
     if *start_tag_name == end_tag_name {
-        let nodes = if position_after_start_tag < position_before_end_tag {
-            vec![crate::Node::Text {
-                end: position_before_end_tag,
-                start: position_after_start_tag,
-                value: &state.wiki_text[position_after_start_tag..position_before_end_tag],
-            }]
-        } else {
-            vec![]
+        let raw_content = crate::RawContent {
+            end: position_before_end_tag,
+            start: position_after_start_tag,
+            value: crate::Cow::Borrowed(
+                &state.wiki_text[position_after_start_tag..position_before_end_tag],
+            ),
         };
+        state.flush(position_before_start_tag);
         state.flushed_position = position_after_end_tag;
         state.scan_position = position_after_end_tag;
         state.nodes.push(crate::Node::Tag {
+            attributes: std::mem::take(attributes),
             end: position_after_end_tag,
             name: end_tag_name,
-            nodes,
+            nodes: vec![],
+            raw_content: Some(raw_content),
             start: position_before_start_tag,
         });
         return true;
@@ -73,7 +73,7 @@ pub fn parse_parameter_separator(state: &mut crate::State) {
     }
 }
 
-pub fn parse_template_end(state: &mut crate::State) {
+pub fn parse_template_end(state: &mut crate::State, configuration: &crate::Configuration) {
     match state.stack.last() {
         Some(crate::OpenNode {
                  type_: crate::OpenNodeType::Function { .. },
@@ -131,7 +131,7 @@ pub fn parse_template_end(state: &mut crate::State) {
                         let nodes = std::mem::replace(&mut state.nodes, nodes);
                         state.nodes.push(crate::Node::Parameter {
                             default: Some(default.unwrap_or(nodes)),
-                            end: state.scan_position,
+                            end: state.scan_position + 3,
                             name,
                             start,
                         });
@@ -141,7 +141,7 @@ pub fn parse_template_end(state: &mut crate::State) {
                         let nodes = std::mem::replace(&mut state.nodes, nodes);
                         state.nodes.push(crate::Node::Parameter {
                             default: None,
-                            end: state.scan_position,
+                            end: state.scan_position + 3,
                             name: nodes,
                             start,
                         });
@@ -177,7 +177,29 @@ pub fn parse_template_end(state: &mut crate::State) {
                 state.scan_position += 2;
                 state.flushed_position = state.scan_position;
                 let name = match name {
-                    None => std::mem::replace(&mut state.nodes, nodes),
+                    None => {
+                        let name = std::mem::replace(&mut state.nodes, nodes);
+                        if let [
+                            crate::Node::Text {
+                                end: name_end,
+                                start: name_start,
+                                ..
+                            },
+                        ] = name[..]
+                        {
+                            let alias = state.wiki_text[name_start..name_end].trim();
+                            if let Some(id) = configuration.variables.get(alias) {
+                                state.nodes.push(crate::Node::Variable {
+                                    end: state.scan_position,
+                                    id: crate::Cow::Owned(id.clone()),
+                                    name: crate::Cow::Borrowed(alias),
+                                    start,
+                                });
+                                return;
+                            }
+                        }
+                        name
+                    }
                     Some(name) => {
                         let parameters_length = parameters.len();
                         let parameter = &mut parameters[parameters_length - 1];
@@ -0,0 +1,176 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+use crate::Cow;
+
+/// Characters that can't appear in titles, other than control characters.
+const INVALID_CHARACTERS: &[char] = &['<', '>', '[', ']', '{', '|', '}'];
+
+/// The number of the file namespace.
+const FILE_NAMESPACE: i32 = 6;
+
+/// The number of the template namespace.
+pub const TEMPLATE_NAMESPACE: i32 = 10;
+
+impl crate::Configuration {
+    /// Parses and normalizes a page title, such as the target of a link, redirect or category.
+    ///
+    /// Character entities are decoded, underscores are replaced by spaces, consecutive whitespace is replaced by a single space, and whitespace is trimmed from both ends. A leading colon is ignored. Then the title is split into an interwiki prefix or a namespace, which are recognized without regard to case, the page name and a fragment after `#`. On wikis where the first letter of page names is uppercase, the first letter of the page name is converted to uppercase, except for titles on other wikis.
+    ///
+    /// Returns `None` if the text isn't a valid title, such as if it contains characters that can't appear in titles or has a namespace but no page name.
+    ///
+    /// ```
+    /// use parse_wiki_text_2::Configuration;
+    ///
+    /// let configuration = Configuration::default();
+    /// let title = configuration.title("image:foo__bar.png#Usage").unwrap();
+    /// assert_eq!(title.namespace, 6);
+    /// assert_eq!(title.to_string(), "File:Foo bar.png#Usage");
+    /// assert_eq!(configuration.title("foo_bar"), configuration.title(" Foo bar "));
+    /// ```
+    #[must_use]
+    pub fn title(&self, text: &str) -> Option<crate::Title> {
+        self.title_in_namespace(text, 0)
+    }
+
+    /// Parses and normalizes the target of an image in a gallery the same as [`title`](Self::title), except that a target without a namespace refers to a file, unless it starts with a colon.
+    #[must_use]
+    pub fn file_title(&self, target: &str) -> Option<crate::Title> {
+        self.title_in_namespace(target, FILE_NAMESPACE)
+    }
+
+    /// Parses and normalizes the name of a template the same as [`title`](Self::title), except that a name without a namespace refers to a page in the template namespace, unless it starts with a colon.
+    #[must_use]
+    pub fn template_title(&self, name: &str) -> Option<crate::Title> {
+        self.title_in_namespace(name, TEMPLATE_NAMESPACE)
+    }
+
+    fn title_in_namespace(&self, text: &str, default_namespace: i32) -> Option<crate::Title> {
+        let text = normalize_whitespace(&decode_character_entities(text));
+        let (text, mut namespace) = match text.strip_prefix(':') {
+            None => (text.as_str(), default_namespace),
+            Some(text) => (text.trim_start(), 0),
+        };
+        let (mut page_name, fragment) = match text.split_once('#') {
+            None => (text, None),
+            Some((page_name, fragment)) => (
+                page_name.trim_end(),
+                Some(fragment.trim_start()).filter(|fragment| !fragment.is_empty()),
+            ),
+        };
+        let mut interwiki = None;
+        if let Some((prefix, rest)) = page_name.split_once(':') {
+            if let Some(id) = self.namespace_ids.get(&fold_name(prefix)) {
+                namespace = *id;
+                page_name = rest.trim_start();
+            } else if self
+                .interwiki
+                .contains_key(&prefix.trim_end().to_lowercase())
+            {
+                interwiki = Some(prefix.trim_end().to_lowercase());
+                namespace = 0;
+                page_name = rest.trim_start();
+            }
+        }
+        if page_name.contains(|character: char| {
+            character.is_control() || INVALID_CHARACTERS.contains(&character)
+        }) || page_name.is_empty()
+            && interwiki.is_none()
+            && (namespace != 0 || fragment.is_none())
+        {
+            return None;
+        }
+        let page_name = if self.capital_links && interwiki.is_none() {
+            uppercase_first(page_name)
+        } else {
+            page_name.to_string()
+        };
+        Some(crate::Title {
+            fragment: fragment.map(str::to_string),
+            interwiki,
+            namespace_name: self
+                .namespace_names
+                .get(&namespace)
+                .cloned()
+                .unwrap_or_default(),
+            namespace,
+            page_name,
+        })
+    }
+}
+
+impl std::fmt::Display for crate::Title {
+    /// Writes the title in its canonical form, such as `fr:Paris` or `Help:Contents#Editing`.
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(interwiki) = &self.interwiki {
+            write!(formatter, "{}:", interwiki)?;
+        }
+        if !self.namespace_name.is_empty() {
+            write!(formatter, "{}:", self.namespace_name)?;
+        }
+        formatter.write_str(&self.page_name)?;
+        if let Some(fragment) = &self.fragment {
+            write!(formatter, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Normalizes a name to compare it without regard to case and to how words are separated, such as the name of a namespace.
+pub fn fold_name(name: &str) -> String {
+    normalize_whitespace(name).to_lowercase()
+}
+
+/// Decodes the character entities in a text.
+fn decode_character_entities(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+    let mut decoded = String::new();
+    let mut rest = text;
+    while let Some(position) = rest.find('&') {
+        decoded.push_str(&rest[..position]);
+        rest = &rest[position..];
+        match rest.split_inclusive(';').next().and_then(|entity| {
+            Some((
+                entity.len(),
+                crate::character_entity::decode_character_entity(entity)?,
+            ))
+        }) {
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+            Some((length, character)) => {
+                decoded.push(character);
+                rest = &rest[length..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    Cow::Owned(decoded)
+}
+
+/// Replaces underscores by spaces and consecutive whitespace by a single space, and trims whitespace from both ends.
+fn normalize_whitespace(text: &str) -> String {
+    text.replace('_', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Converts the first letter of a text to uppercase, unless the uppercase form is more than one character.
+fn uppercase_first(text: &str) -> String {
+    let mut characters = text.chars();
+    match characters.next() {
+        None => String::new(),
+        Some(first) => {
+            let mut uppercase = first.to_uppercase();
+            match (uppercase.next(), uppercase.next()) {
+                (Some(upper), None) => std::iter::once(upper).chain(characters).collect(),
+                _ => text.to_string(),
+            }
+        }
+    }
+}
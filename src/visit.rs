@@ -0,0 +1,426 @@
+// Copyright 2019 Fredrik Portström <https://portstrom.com>
+// This is free software distributed under the terms specified in
+// the file LICENSE at the top-level directory of this distribution.
+
+macro_rules! define_visit {
+    ($(#[$attribute:meta])* $trait:ident $(, $mut:tt)?) => {
+        $(#[$attribute])*
+        pub trait $trait<'a> {
+            /// Visits a node by calling the hook for its type.
+            fn visit_node(&mut self, node: &$($mut)? crate::Node<'a>) {
+                match node {
+                    crate::Node::Bold { .. } => self.visit_bold(node),
+                    crate::Node::BoldItalic { .. } => self.visit_bold_italic(node),
+                    crate::Node::Category { .. } => self.visit_category(node),
+                    crate::Node::CharacterEntity { .. } => self.visit_character_entity(node),
+                    crate::Node::Comment { .. } => self.visit_comment(node),
+                    crate::Node::DefinitionList { .. } => self.visit_definition_list(node),
+                    crate::Node::EndTag { .. } => self.visit_end_tag(node),
+                    crate::Node::ExternalLink { .. } => self.visit_external_link(node),
+                    crate::Node::Function { .. } => self.visit_function(node),
+                    crate::Node::Gallery { .. } => self.visit_gallery(node),
+                    crate::Node::Heading { .. } => self.visit_heading(node),
+                    crate::Node::HorizontalDivider { .. } => self.visit_horizontal_divider(node),
+                    crate::Node::Ignored { .. } => self.visit_ignored(node),
+                    crate::Node::Image { .. } => self.visit_image(node),
+                    crate::Node::Italic { .. } => self.visit_italic(node),
+                    crate::Node::LanguageLink { .. } => self.visit_language_link(node),
+                    crate::Node::Link { .. } => self.visit_link(node),
+                    crate::Node::MagicLink { .. } => self.visit_magic_link(node),
+                    crate::Node::MagicWord { .. } => self.visit_magic_word(node),
+                    crate::Node::OrderedList { .. } => self.visit_ordered_list(node),
+                    crate::Node::ParagraphBreak { .. } => self.visit_paragraph_break(node),
+                    crate::Node::Parameter { .. } => self.visit_parameter(node),
+                    crate::Node::Preformatted { .. } => self.visit_preformatted(node),
+                    crate::Node::Redirect { .. } => self.visit_redirect(node),
+                    crate::Node::StartTag { .. } => self.visit_start_tag(node),
+                    crate::Node::Table { .. } => self.visit_table(node),
+                    crate::Node::Tag { .. } => self.visit_tag(node),
+                    crate::Node::Template { .. } => self.visit_template(node),
+                    crate::Node::Text { .. } => self.visit_text(node),
+                    crate::Node::UnorderedList { .. } => self.visit_unordered_list(node),
+                    crate::Node::Variable { .. } => self.visit_variable(node),
+                }
+            }
+
+            /// Visits each of the nodes in order.
+            fn visit_nodes(&mut self, nodes: &$($mut)? [crate::Node<'a>]) {
+                for node in nodes {
+                    self.visit_node(node);
+                }
+            }
+
+            /// Visits the children of a node, in the order they appear in the wiki text. This is what the default implementations of the hooks for the types of nodes do, and can be called from a hook that is overridden to continue the traversal.
+            fn visit_children(&mut self, node: &$($mut)? crate::Node<'a>) {
+                match node {
+                    crate::Node::Bold { .. }
+                    | crate::Node::BoldItalic { .. }
+                    | crate::Node::CharacterEntity { .. }
+                    | crate::Node::Comment { .. }
+                    | crate::Node::EndTag { .. }
+                    | crate::Node::HorizontalDivider { .. }
+                    | crate::Node::Ignored { .. }
+                    | crate::Node::Italic { .. }
+                    | crate::Node::MagicLink { .. }
+                    | crate::Node::MagicWord { .. }
+                    | crate::Node::ParagraphBreak { .. }
+                    | crate::Node::Redirect { .. }
+                    | crate::Node::Text { .. }
+                    | crate::Node::Variable { .. } => {}
+                    crate::Node::Category { ordinal, .. } => self.visit_nodes(ordinal),
+                    crate::Node::DefinitionList { items, .. } => {
+                        for item in items {
+                            self.visit_definition_list_item(item);
+                        }
+                    }
+                    crate::Node::ExternalLink { label: nodes, .. }
+                    | crate::Node::Heading { nodes, .. }
+                    | crate::Node::Preformatted { nodes, .. } => self.visit_nodes(nodes),
+                    crate::Node::Function {
+                        name, parameters, ..
+                    } => {
+                        self.visit_nodes(name);
+                        for parameter in parameters {
+                            self.visit_function_parameter(parameter);
+                        }
+                    }
+                    crate::Node::Gallery {
+                        attributes,
+                        entries,
+                        ..
+                    } => {
+                        for attribute in attributes {
+                            self.visit_attribute(attribute);
+                        }
+                        for entry in entries {
+                            self.visit_gallery_entry(entry);
+                        }
+                    }
+                    crate::Node::Image { options, .. } => {
+                        for option in options {
+                            self.visit_image_option(option);
+                        }
+                    }
+                    crate::Node::LanguageLink { text, .. } | crate::Node::Link { text, .. } => {
+                        self.visit_nodes(text)
+                    }
+                    crate::Node::OrderedList { items, .. }
+                    | crate::Node::UnorderedList { items, .. } => {
+                        for item in items {
+                            self.visit_list_item(item);
+                        }
+                    }
+                    crate::Node::Parameter { default, name, .. } => {
+                        self.visit_nodes(name);
+                        if let Some(default) = default {
+                            self.visit_nodes(default);
+                        }
+                    }
+                    crate::Node::StartTag { attributes, .. } => {
+                        for attribute in attributes {
+                            self.visit_attribute(attribute);
+                        }
+                    }
+                    crate::Node::Table {
+                        attributes,
+                        captions,
+                        rows,
+                        ..
+                    } => {
+                        for attribute in attributes {
+                            self.visit_table_attribute(attribute);
+                        }
+                        for caption in captions {
+                            self.visit_table_caption(caption);
+                        }
+                        for row in rows {
+                            self.visit_table_row(row);
+                        }
+                    }
+                    crate::Node::Tag {
+                        attributes, nodes, ..
+                    } => {
+                        for attribute in attributes {
+                            self.visit_attribute(attribute);
+                        }
+                        self.visit_nodes(nodes);
+                    }
+                    crate::Node::Template {
+                        name, parameters, ..
+                    } => {
+                        self.visit_nodes(name);
+                        for parameter in parameters {
+                            self.visit_template_parameter(parameter);
+                        }
+                    }
+                }
+            }
+
+            /// Visits an HTML attribute of a start tag or extension tag.
+            fn visit_attribute(&mut self, _attribute: &$($mut)? crate::Attribute<'a>) {}
+
+            /// Visits a [`Node::Bold`](crate::Node::Bold).
+            fn visit_bold(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::BoldItalic`](crate::Node::BoldItalic).
+            fn visit_bold_italic(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Category`](crate::Node::Category).
+            fn visit_category(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::CharacterEntity`](crate::Node::CharacterEntity).
+            fn visit_character_entity(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Comment`](crate::Node::Comment).
+            fn visit_comment(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::DefinitionList`](crate::Node::DefinitionList).
+            fn visit_definition_list(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a list item of a definition list.
+            fn visit_definition_list_item(&mut self, item: &$($mut)? crate::DefinitionListItem<'a>) {
+                self.visit_nodes(&$($mut)? item.nodes);
+            }
+
+            /// Visits a [`Node::EndTag`](crate::Node::EndTag).
+            fn visit_end_tag(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::ExternalLink`](crate::Node::ExternalLink).
+            fn visit_external_link(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Function`](crate::Node::Function).
+            fn visit_function(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a parameter of a function.
+            fn visit_function_parameter(&mut self, parameter: &$($mut)? crate::FunctionParameter<'a>) {
+                self.visit_nodes(&$($mut)? parameter.value);
+            }
+
+            /// Visits a [`Node::Gallery`](crate::Node::Gallery).
+            fn visit_gallery(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits an image of a gallery.
+            fn visit_gallery_entry(&mut self, entry: &$($mut)? crate::GalleryEntry<'a>) {
+                for option in &$($mut)? entry.options {
+                    self.visit_image_option(option);
+                }
+            }
+
+            /// Visits a [`Node::Heading`](crate::Node::Heading).
+            fn visit_heading(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::HorizontalDivider`](crate::Node::HorizontalDivider).
+            fn visit_horizontal_divider(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Ignored`](crate::Node::Ignored).
+            fn visit_ignored(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Image`](crate::Node::Image).
+            fn visit_image(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits an option of an image.
+            fn visit_image_option(&mut self, option: &$($mut)? crate::ImageOption<'a>) {
+                self.visit_nodes(&$($mut)? option.value);
+            }
+
+            /// Visits a [`Node::Italic`](crate::Node::Italic).
+            fn visit_italic(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::LanguageLink`](crate::Node::LanguageLink).
+            fn visit_language_link(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Link`](crate::Node::Link).
+            fn visit_link(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a list item of an ordered list or unordered list.
+            fn visit_list_item(&mut self, item: &$($mut)? crate::ListItem<'a>) {
+                self.visit_nodes(&$($mut)? item.nodes);
+            }
+
+            /// Visits a [`Node::MagicLink`](crate::Node::MagicLink).
+            fn visit_magic_link(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::MagicWord`](crate::Node::MagicWord).
+            fn visit_magic_word(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::OrderedList`](crate::Node::OrderedList).
+            fn visit_ordered_list(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::ParagraphBreak`](crate::Node::ParagraphBreak).
+            fn visit_paragraph_break(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Parameter`](crate::Node::Parameter).
+            fn visit_parameter(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Preformatted`](crate::Node::Preformatted).
+            fn visit_preformatted(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Redirect`](crate::Node::Redirect).
+            fn visit_redirect(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::StartTag`](crate::Node::StartTag).
+            fn visit_start_tag(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Table`](crate::Node::Table).
+            fn visit_table(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits an HTML attribute of a table, table caption, table row or table cell.
+            fn visit_table_attribute(&mut self, attribute: &$($mut)? crate::TableAttribute<'a>) {
+                self.visit_nodes(&$($mut)? attribute.name_nodes);
+                if let Some(value) = &$($mut)? attribute.value {
+                    self.visit_nodes(&$($mut)? value.nodes);
+                }
+            }
+
+            /// Visits a caption of a table.
+            fn visit_table_caption(&mut self, caption: &$($mut)? crate::TableCaption<'a>) {
+                if let Some(attributes) = &$($mut)? caption.attributes {
+                    for attribute in attributes {
+                        self.visit_table_attribute(attribute);
+                    }
+                }
+                self.visit_nodes(&$($mut)? caption.content);
+            }
+
+            /// Visits a cell of a table row.
+            fn visit_table_cell(&mut self, cell: &$($mut)? crate::TableCell<'a>) {
+                if let Some(attributes) = &$($mut)? cell.attributes {
+                    for attribute in attributes {
+                        self.visit_table_attribute(attribute);
+                    }
+                }
+                self.visit_nodes(&$($mut)? cell.content);
+            }
+
+            /// Visits a row of a table.
+            fn visit_table_row(&mut self, row: &$($mut)? crate::TableRow<'a>) {
+                for attribute in &$($mut)? row.attributes {
+                    self.visit_table_attribute(attribute);
+                }
+                for cell in &$($mut)? row.cells {
+                    self.visit_table_cell(cell);
+                }
+            }
+
+            /// Visits a [`Node::Tag`](crate::Node::Tag).
+            fn visit_tag(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Template`](crate::Node::Template).
+            fn visit_template(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a parameter of a template.
+            fn visit_template_parameter(&mut self, parameter: &$($mut)? crate::Parameter<'a>) {
+                if let Some(name) = &$($mut)? parameter.name {
+                    self.visit_nodes(name);
+                }
+                self.visit_nodes(&$($mut)? parameter.value);
+            }
+
+            /// Visits a [`Node::Text`](crate::Node::Text).
+            fn visit_text(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::UnorderedList`](crate::Node::UnorderedList).
+            fn visit_unordered_list(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+
+            /// Visits a [`Node::Variable`](crate::Node::Variable).
+            fn visit_variable(&mut self, node: &$($mut)? crate::Node<'a>) {
+                self.visit_children(node);
+            }
+        }
+    };
+}
+
+define_visit!(
+    /// Traversal of a tree of nodes.
+    ///
+    /// Each type of node has a hook, which by default visits the children of the node. Override the hooks for the types of nodes of interest, and call [`visit_children`](Visit::visit_children) from the hook to also visit the nodes within. The traversal is started by calling [`visit_nodes`](Visit::visit_nodes) with the nodes of an [`Output`](crate::Output).
+    ///
+    /// ```
+    /// use parse_wiki_text_2::{Configuration, Node, Visit};
+    ///
+    /// struct LinkTargets(Vec<String>);
+    ///
+    /// impl<'a> Visit<'a> for LinkTargets {
+    ///     fn visit_link(&mut self, node: &Node<'a>) {
+    ///         if let Node::Link { target, .. } = node {
+    ///             self.0.push(target.to_string());
+    ///         }
+    ///         self.visit_children(node);
+    ///     }
+    /// }
+    ///
+    /// let output = Configuration::default().parse("* [[a]]\n* {{b|[[c]]}}").unwrap();
+    /// let mut targets = LinkTargets(vec![]);
+    /// targets.visit_nodes(&output.nodes);
+    /// assert_eq!(targets.0, ["a", "c"]);
+    /// ```
+    Visit
+);
+
+define_visit!(
+    /// Traversal of a tree of nodes that can modify the nodes visited.
+    ///
+    /// This works the same way as [`Visit`], except that the hooks get mutable references.
+    VisitMut,
+    mut
+);
@@ -6,6 +6,7 @@ use std::fmt;
 
 /// Warning from the parser telling that something is not well-formed.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Warning {
     /// The byte position in the wiki text where the warning ends.
     pub end: usize,
@@ -19,7 +20,11 @@ pub struct Warning {
 
 /// Identifier for a kind of warning from the parser.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum WarningMessage {
+    /// Named reference defined more than once with different content.
+    ConflictingReference,
+
     /// List broken by definition term.
     DefinitionTermContinuation,
 
@@ -29,9 +34,15 @@ pub enum WarningMessage {
     /// Invalid character.
     InvalidCharacter,
 
+    /// Numeric character reference to a code point that is not allowed.
+    InvalidCharacterReference,
+
     /// Invalid heading syntax. Rewinding.
     InvalidHeadingSyntaxRewinding,
 
+    /// ISBN with an invalid check digit.
+    InvalidIsbnChecksum,
+
     /// Invalid link syntax.
     InvalidLinkSyntax,
 
@@ -53,6 +64,9 @@ pub enum WarningMessage {
     /// Wiki text comes after a redirect.
     TextAfterRedirect,
 
+    /// Reference used without being defined.
+    UndefinedReference,
+
     /// The end tag does not match the last start tag. Rewinding.
     UnexpectedEndTagRewinding,
 
@@ -65,6 +79,9 @@ pub enum WarningMessage {
     /// A tag with an unrecognized tag name was found.
     UnrecognizedTagName,
 
+    /// Reference defined in a list of references but not used in the text.
+    UnusedReference,
+
     /// Useless text in parameter.
     UselessTextInParameter,
 
@@ -76,10 +93,17 @@ impl WarningMessage {
     /// Human-readable description of the warning.
     pub fn message(self) -> &'static str {
         match self {
+            WarningMessage::ConflictingReference => {
+                "Named reference defined more than once with different content."
+            }
             WarningMessage::DefinitionTermContinuation => "List broken by definition term.",
             WarningMessage::EndTagInComment => "End tag in comment.",
             WarningMessage::InvalidCharacter => "Invalid character.",
+            WarningMessage::InvalidCharacterReference => {
+                "Numeric character reference to a code point that is not allowed."
+            }
             WarningMessage::InvalidHeadingSyntaxRewinding => "Invalid heading syntax. Rewinding.",
+            WarningMessage::InvalidIsbnChecksum => "ISBN with an invalid check digit.",
             WarningMessage::InvalidLinkSyntax => "Invalid link syntax.",
             WarningMessage::InvalidParameterSyntax => "Invalid parameter syntax.",
             WarningMessage::InvalidTagSyntax => "Invalid tag syntax.",
@@ -87,6 +111,7 @@ impl WarningMessage {
             WarningMessage::RepeatedEmptyLine => "Repeated empty line.",
             WarningMessage::StrayTextInTable => "Stray text in table.",
             WarningMessage::TextAfterRedirect => "Wiki text comes after a redirect.",
+            WarningMessage::UndefinedReference => "Reference used without being defined.",
             WarningMessage::UnexpectedEndTagRewinding => {
                 "The end tag does not match the last start tag. Rewinding."
             }
@@ -97,6 +122,9 @@ impl WarningMessage {
                 "Expected heading of higher level. Correcting start of heading."
             }
             WarningMessage::UnrecognizedTagName => "A tag with an unrecognized tag name was found.",
+            WarningMessage::UnusedReference => {
+                "Reference defined in a list of references but not used in the text."
+            }
             WarningMessage::UselessTextInParameter => "Useless text in parameter.",
             WarningMessage::UselessTextInRedirect => "Useless text in redirect.",
         }
@@ -0,0 +1,169 @@
+use parse_wiki_text_2::{Configuration, ConfigurationSource, Node, TagContentModel};
+
+#[test]
+fn test_localized_magic_words() {
+    let configuration = Configuration::new(&ConfigurationSource {
+        capital_links: true,
+        category_namespaces: &["kategorie"],
+        extension_tags: &[],
+        file_namespaces: &["datei"],
+        image_options: &[],
+        interwiki: &[],
+        link_prefix: "",
+        link_trail: "abcdefghijklmnopqrstuvwxyzäöüß",
+        magic_links: &[],
+        magic_words: &[
+            ("INHALTSVERZEICHNIS_ERZWINGEN", "FORCETOC"),
+            ("KEININHALTSVERZEICHNIS", "NOTOC"),
+            ("KEINVERZEICHNIS", "NOTOC"),
+            ("NOTOC", "NOTOC"),
+        ],
+        namespaces: &[],
+        protocols: &["https://"],
+        redirect_magic_words: &["WEITERLEITUNG"],
+        variables: &[],
+    });
+    let s = "__KEINVERZEICHNIS__ __keininhaltsverzeichnis__ __NOTOC__ __INHALTSVERZEICHNIS_ERZWINGEN__ __TOC__";
+    let output = configuration.parse(s).unwrap();
+    let ids: Vec<_> = output
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::MagicWord { id, .. } => Some(id.as_ref()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(ids, ["NOTOC", "NOTOC", "NOTOC", "FORCETOC"]);
+    assert_eq!(output.to_wikitext(s), s);
+}
+
+#[test]
+fn test_link_prefix() {
+    let configuration = Configuration::new(&ConfigurationSource {
+        capital_links: true,
+        category_namespaces: &[],
+        extension_tags: &[],
+        file_namespaces: &[],
+        image_options: &[],
+        interwiki: &[],
+        link_prefix: "abcdefghijklmnopqrstuvwxyz",
+        link_trail: "abcdefghijklmnopqrstuvwxyz",
+        magic_links: &[],
+        magic_words: &[],
+        namespaces: &[],
+        protocols: &[],
+        redirect_magic_words: &[],
+        variables: &[],
+    });
+    let s = "alpha beta[[gamma|delta]]epsilon [[zeta]]";
+    let output = configuration.parse(s).unwrap();
+    match &output.nodes[..] {
+        [
+            Node::Text { value, .. },
+            Node::Link {
+                end,
+                prefix: Some(prefix),
+                start,
+                trail: Some(trail),
+                ..
+            },
+            Node::Text { .. },
+            Node::Link {
+                prefix: None,
+                trail: None,
+                ..
+            },
+        ] => {
+            assert_eq!(value, "alpha ");
+            assert_eq!(prefix.value, "beta");
+            assert_eq!(trail.value, "epsilon");
+            assert_eq!(&s[*start..*end], "beta[[gamma|delta]]epsilon");
+        }
+        nodes => panic!("unexpected nodes: {:?}", nodes),
+    }
+    assert_eq!(output.to_plain_text(), "alpha betadeltaepsilon zeta");
+    assert_eq!(output.to_wikitext(s), s);
+}
+
+#[test]
+fn test_extension_tag_content_models() {
+    let configuration = Configuration::new(&ConfigurationSource {
+        capital_links: true,
+        category_namespaces: &[],
+        extension_tags: &[
+            ("listing", TagContentModel::Raw),
+            ("note", TagContentModel::Wikitext),
+        ],
+        file_namespaces: &[],
+        image_options: &[],
+        interwiki: &[],
+        link_prefix: "",
+        link_trail: "",
+        magic_links: &[],
+        magic_words: &[],
+        namespaces: &[],
+        protocols: &[],
+        redirect_magic_words: &[],
+        variables: &[],
+    });
+    let s = "<listing>''alpha''</listing><note>''beta''</note>";
+    let output = configuration.parse(s).unwrap();
+    match &output.nodes[..] {
+        [
+            Node::Tag {
+                nodes: listing_nodes,
+                raw_content: Some(raw_content),
+                ..
+            },
+            Node::Tag {
+                nodes: note_nodes,
+                raw_content: None,
+                ..
+            },
+        ] => {
+            assert!(listing_nodes.is_empty());
+            assert_eq!(raw_content.value, "''alpha''");
+            assert_eq!(&s[raw_content.start..raw_content.end], "''alpha''");
+            assert_eq!(note_nodes.len(), 3);
+        }
+        nodes => panic!("unexpected nodes: {:?}", nodes),
+    }
+    assert_eq!(output.to_wikitext(s), s);
+}
+
+#[test]
+fn test_gallery_content_model() {
+    let configuration = Configuration::new(&ConfigurationSource {
+        capital_links: true,
+        category_namespaces: &[],
+        extension_tags: &[("galerie", TagContentModel::Gallery)],
+        file_namespaces: &[],
+        image_options: &[],
+        interwiki: &[],
+        link_prefix: "",
+        link_trail: "",
+        magic_links: &[],
+        magic_words: &[],
+        namespaces: &[("Datei", 6)],
+        protocols: &[],
+        redirect_magic_words: &[],
+        variables: &[],
+    });
+    let s = "<galerie>\nalpha.jpg|beta\n</galerie><gallery>\ngamma.jpg\n</gallery>";
+    let output = configuration.parse(s).unwrap();
+    match &output.nodes[..] {
+        [
+            Node::Gallery { entries, name, .. },
+            Node::Text { value, .. },
+        ] => {
+            assert_eq!(name, "galerie");
+            assert_eq!(
+                entries[0].title.as_ref().unwrap().to_string(),
+                "Datei:Alpha.jpg"
+            );
+            assert_eq!(*value, "<gallery>\ngamma.jpg\n</gallery>");
+        }
+        nodes => panic!("unexpected nodes: {:?}", nodes),
+    }
+    assert_eq!(output.to_wikitext(s), s);
+}
@@ -0,0 +1,259 @@
+use parse_wiki_text_2::{
+    Configuration, ExpansionContext, Node, Page, PageContext, ParserFunctions, TemplateProvider,
+};
+use std::{borrow::Cow, collections::HashMap, fs};
+
+const PAGE: Page = Page {
+    current_time: 0,
+    namespace: "",
+    page_name: "Test",
+};
+
+/// Reads templates from files in the directory `tests/templates`.
+struct Directory;
+
+impl TemplateProvider for Directory {
+    fn template(&self, name: &str) -> Option<Cow<'_, str>> {
+        fs::read_to_string(format!("./tests/templates/{}.wiki", name))
+            .ok()
+            .map(Cow::Owned)
+    }
+}
+
+fn expand(wiki_text: &str, templates: &[(&str, &str)]) -> String {
+    let templates: HashMap<String, String> = templates
+        .iter()
+        .map(|(name, wiki_text)| (name.to_string(), wiki_text.to_string()))
+        .collect();
+    let context = ExpansionContext {
+        functions: &ParserFunctions::new(),
+        page: &PAGE,
+        templates: &templates,
+    };
+    Configuration::default()
+        .expand(wiki_text, &context)
+        .unwrap()
+        .wiki_text
+}
+
+#[test]
+fn test_parameters() {
+    let templates = [("A", "<{{{1}}}|{{{2|b}}}|{{{c}}}|{{{d|}}}>")];
+    assert_eq!(expand("{{a| x |c = y }}", &templates), "< x |b|y|>");
+    assert_eq!(expand("{{a|2=x|y}}", &templates), "<y|x|{{{c}}}|>");
+}
+
+#[test]
+fn test_nested() {
+    let templates = [
+        ("Outer", "({{inner|{{{1}}}}})"),
+        ("Inner", "[{{{1}}}]"),
+        ("Name", "inner"),
+    ];
+    assert_eq!(
+        expand("{{Outer|a}} {{ {{name}} |b}}", &templates),
+        "([a]) [b]"
+    );
+}
+
+#[test]
+fn test_unknown_template() {
+    assert_eq!(expand("a {{b|{{c}}}} d", &[("C", "x")]), "a {{b|x}} d");
+}
+
+#[test]
+fn test_loop() {
+    let templates = [("A", "{{b}}"), ("B", "{{a}}")];
+    assert_eq!(
+        expand("{{a}}", &templates),
+        "<span class=\"error\">Template loop detected: [[Template:A]]</span>"
+    );
+}
+
+#[test]
+fn test_directory() {
+    let expansion = Configuration::default()
+        .expand(
+            "{{Infobox_person|name=Ada|born=1815}}\n{{bracket|x}}",
+            &ExpansionContext {
+                functions: &ParserFunctions::new(),
+                page: &PAGE,
+                templates: &Directory,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        expansion.wiki_text,
+        "{| class=\"infobox\"\n! Ada\n|-\n| Born: 1815\n|}\n[x]"
+    );
+    assert!(matches!(expansion.output.nodes[0], Node::Table { .. }));
+    assert_eq!(
+        expansion.output.to_wikitext(&expansion.wiki_text),
+        expansion.wiki_text
+    );
+}
+
+#[test]
+fn test_variables() {
+    let page = Page {
+        current_time: 1_234_567_890,
+        namespace: "User talk",
+        page_name: "Ada Lovelace/Notes",
+    };
+    let context = ExpansionContext {
+        functions: &ParserFunctions::new(),
+        page: &page,
+        templates: &HashMap::<String, String>::new(),
+    };
+    let expand = |wiki_text| {
+        Configuration::default()
+            .expand(wiki_text, &context)
+            .unwrap()
+            .wiki_text
+    };
+    assert_eq!(
+        expand("{{PAGENAME}}|{{FULLPAGENAMEE}}|{{NAMESPACE}}|{{BASEPAGENAME}}|{{SUBPAGENAME}}"),
+        "Ada Lovelace/Notes|User_talk:Ada_Lovelace/Notes|User talk|Ada Lovelace|Notes"
+    );
+    assert_eq!(
+        expand(
+            "{{CURRENTYEAR}}-{{CURRENTMONTH}}-{{CURRENTDAY2}} {{CURRENTTIME}} {{CURRENTDAYNAME}}"
+        ),
+        "2009-02-13 23:31 Friday"
+    );
+    assert_eq!(
+        expand("{{CURRENTTIMESTAMP}} {{CURRENTWEEK}} {{CURRENTMONTHABBREV}} {{CURRENTDOW}}"),
+        "20090213233130 7 Feb 5"
+    );
+    assert_eq!(expand("{{#if:{{PAGENAME}}|a{{!}}b}}"), "a|b");
+}
+
+#[test]
+fn test_current_week() {
+    let week = |current_time| {
+        let context = ExpansionContext {
+            functions: &ParserFunctions::new(),
+            page: &Page {
+                current_time,
+                namespace: "",
+                page_name: "Test",
+            },
+            templates: &HashMap::<String, String>::new(),
+        };
+        Configuration::default()
+            .expand("{{CURRENTWEEK}} {{CURRENTYEAR}}", &context)
+            .unwrap()
+            .wiki_text
+    };
+    // 2021-01-03 belongs to the last week of 2020, and 2024-12-30 to the first week of 2025.
+    assert_eq!(week(1_609_675_200), "53 2021");
+    assert_eq!(week(1_735_560_000), "1 2024");
+    assert_eq!(week(0), "1 1970");
+}
+
+#[test]
+fn test_unknown_variable() {
+    struct OtherPage;
+
+    impl PageContext for OtherPage {
+        fn current_time(&self) -> i64 {
+            0
+        }
+
+        fn namespace(&self) -> &str {
+            ""
+        }
+
+        fn page_name(&self) -> &str {
+            "Other"
+        }
+
+        fn variable(&self, id: &str) -> Option<String> {
+            match id {
+                "currentyear" => None,
+                _ => PAGE
+                    .variable(id)
+                    .map(|value| value.replace("Test", self.page_name())),
+            }
+        }
+    }
+
+    let context = ExpansionContext {
+        functions: &ParserFunctions::new(),
+        page: &OtherPage,
+        templates: &HashMap::<String, String>::new(),
+    };
+    let expansion = Configuration::default()
+        .expand("{{PAGENAME}} {{CURRENTYEAR}}", &context)
+        .unwrap();
+    assert_eq!(expansion.wiki_text, "Other {{CURRENTYEAR}}");
+}
+
+#[test]
+fn test_external_link_url() {
+    let templates = [
+        ("Host", "example.org"),
+        ("Link", "[http://{{host}}/{{{1}}} {{{1}}}]"),
+    ];
+    assert_eq!(
+        expand("{{link|alpha}} [http://{{host}} beta]", &templates),
+        "[http://example.org/alpha alpha] [http://example.org beta]"
+    );
+}
+
+#[test]
+fn test_inclusion() {
+    let templates = [
+        (
+            "A",
+            "<noinclude>Documentation</noinclude><includeonly>[{{{1}}}]</includeonly>",
+        ),
+        ("B", "x<onlyinclude>({{{1}}})</onlyinclude>y"),
+    ];
+    assert_eq!(
+        expand("{{a|1}} {{b|2}}<includeonly>z</includeonly>", &templates),
+        "[1] (2)"
+    );
+}
+
+#[test]
+fn test_link_target() {
+    let templates = [
+        ("C", "[[Category:{{{1}}}]]"),
+        ("F", "[[File:{{{1}}}|thumb|{{{1}}}]]"),
+        ("L", "[[{{{1}}}]] [[{{{1}}}|alpha]]"),
+        ("T", "Beta"),
+    ];
+    assert_eq!(
+        expand("{{L|Foo}} {{F|Foo.jpg}} {{C|Foo}} [[{{T}}]]", &templates),
+        "[[Foo]] [[Foo|alpha]] [[File:Foo.jpg|thumb|Foo.jpg]] [[Category:Foo]] [[Beta]]"
+    );
+}
+
+#[test]
+fn test_attribute_value() {
+    let templates = [
+        ("R", "<ref name=\"{{{1}}}\">a</ref>"),
+        ("S", "<span class=\"{{{1}}}\">a</span>"),
+    ];
+    assert_eq!(
+        expand("{{S|Foo}} {{R|Foo}}", &templates),
+        "<span class=\"Foo\">a</span> <ref name=\"Foo\">a</ref>"
+    );
+}
+
+#[test]
+fn test_namespaced_transclusion() {
+    let templates = [
+        ("Foo", "alpha"),
+        ("T", "beta {{{1}}} {{{name}}}"),
+        ("User:X/sig", "gamma"),
+    ];
+    assert_eq!(
+        expand(
+            "{{Template:T|q|name=r}} {{:Foo}} {{User:X/sig}} {{User:Y}}",
+            &templates
+        ),
+        "beta q r alpha gamma {{User:Y}}"
+    );
+}
@@ -0,0 +1,15 @@
+use parse_wiki_text_2::{Configuration, IncludeMode, ParseOptions};
+
+const OPTIONS: ParseOptions = ParseOptions {
+    bare_urls: true,
+    include_mode: IncludeMode::View,
+    max_duration: std::time::Duration::ZERO,
+};
+
+#[test]
+fn test_bare_url_plain_text() {
+    let output = Configuration::default()
+        .parse_with_options("see https://example.org/.", &OPTIONS)
+        .unwrap();
+    assert_eq!(output.to_plain_text(), "see https://example.org/.");
+}
@@ -0,0 +1,54 @@
+use parse_wiki_text_2::{Configuration, GalleryEntry, ImageOption, ImageOptionType, Node};
+use std::borrow::Cow;
+
+#[test]
+fn test_gallery_entry_positions() {
+    let wiki_text = "<gallery>\n  File:Alpha.jpg | beta\n{{gamma}}.png\n</gallery>";
+    let output = Configuration::default().parse(wiki_text).unwrap();
+    let Node::Gallery { entries, .. } = &output.nodes[0] else {
+        panic!("{:?}", output.nodes);
+    };
+    let spans: Vec<_> = entries
+        .iter()
+        .map(|entry| (&wiki_text[entry.start..entry.end], entry.target.as_ref()))
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            ("File:Alpha.jpg | beta", "File:Alpha.jpg"),
+            ("{{gamma}}.png", "{{gamma}}.png")
+        ]
+    );
+    let caption = &entries[0].options[0];
+    assert_eq!(&wiki_text[caption.start..caption.end], "beta");
+}
+
+#[test]
+fn test_modified_gallery() {
+    let wiki_text = "<gallery>\nFile:Alpha.jpg|beta\n</gallery>";
+    let mut output = Configuration::default().parse(wiki_text).unwrap();
+    let Node::Gallery { entries, .. } = &mut output.nodes[0] else {
+        panic!("{:?}", output.nodes);
+    };
+    entries[0].options.clear();
+    entries.push(GalleryEntry {
+        end: 0,
+        options: vec![ImageOption {
+            end: 0,
+            start: 0,
+            type_: ImageOptionType::Caption,
+            value: vec![Node::Text {
+                end: 0,
+                start: 0,
+                value: Cow::Borrowed("delta"),
+            }],
+        }],
+        start: 0,
+        target: Cow::Borrowed("File:Gamma.jpg"),
+        title: None,
+    });
+    assert_eq!(
+        output.to_wikitext(wiki_text),
+        "<gallery>\nFile:Alpha.jpg\nFile:Gamma.jpg|delta\n</gallery>"
+    );
+}
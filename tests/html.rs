@@ -0,0 +1,38 @@
+use parse_wiki_text_2::Configuration;
+
+fn to_html(wiki_text: &str) -> String {
+    Configuration::default()
+        .parse(wiki_text)
+        .unwrap()
+        .to_html(|target| format!("/wiki/{}", target))
+}
+
+#[test]
+fn test_paragraphs() {
+    assert_eq!(
+        to_html("{{a}}\nb ''c'''d''e'''\n\n<!--f-->g &amp; [http://h i] [http://j]\n----"),
+        "<p>b <i>c<b>d</b></i><b>e</b></p><p>g &amp; <a class=\"external\" href=\"http://h\">i</a> <a class=\"external\" href=\"http://j\">[1]</a></p><hr>"
+    );
+}
+
+#[test]
+fn test_blocks() {
+    assert_eq!(
+        to_html("=a=\n* b\n*# [[c]]\n; d\n: e\n pre\n{|\n|+ f\n! g\n|-\n| h || '''i\n|}"),
+        concat!(
+            "<h1>a</h1><ul><li>b<ol><li><a href=\"/wiki/c\">c</a></li></ol></li></ul>",
+            "<dl><dt>d</dt><dd>e</dd></dl><pre>pre</pre>",
+            "<table><caption>f</caption><tr><th>g</th></tr><tr><td>h</td><td><b>i</b></td></tr></table>"
+        )
+    );
+}
+
+#[test]
+fn test_tags() {
+    assert_eq!(
+        to_html(
+            "<span class=\"a&amp;b\" onclick=\"c\">d<br/></span><ref>e</ref><nowiki><f></nowiki>"
+        ),
+        "<p><span class=\"a&amp;b\">d<br></span>&lt;f&gt;</p>"
+    );
+}
@@ -0,0 +1,21 @@
+use parse_wiki_text_2::Configuration;
+
+#[test]
+fn test_view_plain_text() {
+    let output = Configuration::default()
+        .parse(
+            "a<includeonly>b</includeonly>c<noinclude>d</noinclude>e<onlyinclude>f</onlyinclude>",
+        )
+        .unwrap();
+    assert_eq!(output.to_plain_text(), "acdef");
+}
+
+#[test]
+fn test_positions() {
+    let output = Configuration::default()
+        .parse("== a ==\n<includeonly>b</includeonly>\n* c")
+        .unwrap();
+    let start = parse_wiki_text_2::Positioned::start(&output.nodes[1]);
+    let end = parse_wiki_text_2::Positioned::end(&output.nodes[1]);
+    assert_eq!((start, end), (8, 36));
+}
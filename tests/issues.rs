@@ -30,3 +30,10 @@ fn issue_1() {
         _ => panic!("expected timeout"),
     }
 }
+
+#[test]
+fn function_keeps_preceding_siblings() {
+    let c = Configuration::default();
+    assert_eq!(c.parse("Hello world.\n{{lc:X}}").unwrap().nodes.len(), 2);
+    assert_eq!(c.parse("a\n{{DEFAULTSORT:X}}\nb").unwrap().nodes.len(), 3);
+}
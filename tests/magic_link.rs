@@ -0,0 +1,67 @@
+use parse_wiki_text_2::{Configuration, ConfigurationSource, MagicLinkType, Node, WarningMessage};
+
+fn configuration() -> Configuration {
+    Configuration::new(&ConfigurationSource {
+        capital_links: true,
+        category_namespaces: &[],
+        extension_tags: &[],
+        file_namespaces: &[],
+        image_options: &[],
+        interwiki: &[],
+        link_prefix: "",
+        link_trail: "abcdefghijklmnopqrstuvwxyz",
+        magic_links: &[MagicLinkType::Isbn, MagicLinkType::Pmid, MagicLinkType::Rfc],
+        magic_words: &[],
+        namespaces: &[],
+        protocols: &["https://"],
+        redirect_magic_words: &[],
+        variables: &[],
+    })
+}
+
+#[test]
+fn test_invalid_isbn_checksum() {
+    let wiki_text = "ISBN 978-3-16-148410-1 ISBN 0306406153";
+    let output = configuration().parse(wiki_text).unwrap();
+    let warnings: Vec<_> = output
+        .warnings
+        .iter()
+        .map(|warning| (warning.start, warning.end, warning.message))
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            (0, 22, WarningMessage::InvalidIsbnChecksum),
+            (23, 38, WarningMessage::InvalidIsbnChecksum),
+        ]
+    );
+}
+
+#[test]
+fn test_magic_link_output() {
+    let output = configuration()
+        .parse("RFC 2616 ISBN 0-306-40615-2")
+        .unwrap();
+    assert_eq!(output.to_plain_text(), "RFC 2616 ISBN 0-306-40615-2");
+    assert_eq!(
+        output.to_html(|target| format!("/wiki/{}", target)),
+        "<p><a class=\"external mw-magiclink-rfc\" href=\"https://datatracker.ietf.org/doc/html/rfc2616\">RFC 2616</a> <a class=\"internal mw-magiclink-isbn\" href=\"/wiki/Special:BookSources/0306406152\">ISBN 0-306-40615-2</a></p>"
+    );
+}
+
+#[test]
+fn test_modified_magic_links() {
+    let wiki_text = "ISBN 0-306-40615-2 PMID 123 RFC 2616";
+    let mut output = configuration().parse(wiki_text).unwrap();
+    assert_eq!(output.to_wikitext(wiki_text), wiki_text);
+    if let Node::MagicLink { raw_id, .. } = &mut output.nodes[0] {
+        *raw_id = "0306406152".into();
+    }
+    if let Node::MagicLink { id, .. } = &mut output.nodes[2] {
+        *id = "456".into();
+    }
+    assert_eq!(
+        output.to_wikitext(wiki_text),
+        "ISBN 0306406152 PMID 456 RFC 2616"
+    );
+}
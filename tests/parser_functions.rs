@@ -0,0 +1,174 @@
+use parse_wiki_text_2::{Configuration, ExpansionContext, Page, ParserFunctions};
+use std::collections::HashMap;
+
+const PAGE: Page = Page {
+    current_time: 0,
+    namespace: "",
+    page_name: "Test",
+};
+
+fn expand(wiki_text: &str) -> String {
+    let templates: HashMap<String, String> =
+        [("Echo", "{{{1|}}}"), ("Is", "{{#if:{{{1|}}}|yes|no}}")]
+            .iter()
+            .map(|(name, wiki_text)| (name.to_string(), wiki_text.to_string()))
+            .collect();
+    let context = ExpansionContext {
+        functions: &ParserFunctions::new(),
+        page: &PAGE,
+        templates: &templates,
+    };
+    Configuration::default()
+        .expand(wiki_text, &context)
+        .unwrap()
+        .wiki_text
+}
+
+#[test]
+fn test_if() {
+    assert_eq!(expand("{{#if: a | b | c }}"), "b");
+    assert_eq!(expand("{{#if: | b | c }}"), "c");
+    assert_eq!(expand("{{#if: {{echo| }} | b }}"), "");
+    assert_eq!(expand("<{{is|a}}|{{is}}>"), "<yes|no>");
+    assert_eq!(expand("{{#IF: a | [[b|c]] }}"), "[[b|c]]");
+}
+
+#[test]
+fn test_ifeq() {
+    assert_eq!(expand("{{#ifeq: a | a | b | c }}"), "b");
+    assert_eq!(expand("{{#ifeq: a | A | b | c }}"), "c");
+    assert_eq!(expand("{{#ifeq: 01 | 1.0 | b | c }}"), "b");
+    assert_eq!(expand("{{#ifeq: {{echo|x}} | x | b | c }}"), "b");
+}
+
+#[test]
+fn test_iferror() {
+    assert_eq!(expand("{{#iferror: {{#expr: 1/0}} | a | b }}"), "a");
+    assert_eq!(expand("{{#iferror: {{#expr: 1+1}} | a | b }}"), "b");
+    assert_eq!(expand("{{#iferror: {{#expr: 1+1}} | a }}"), "2");
+    assert_eq!(expand("{{#iferror: {{#expr: 1/0}} }}"), "");
+    assert_eq!(
+        expand("{{#iferror: <span id=\"a\" class=\"b error\">c</span> | d }}"),
+        "d"
+    );
+}
+
+#[test]
+fn test_switch() {
+    let s = "{{#switch: {{echo|{{{1|}}}}} | a = x | b | c = y | #default = z }}";
+    assert_eq!(expand(&s.replace("{{{1|}}}", "a")), "x");
+    assert_eq!(expand(&s.replace("{{{1|}}}", "b")), "y");
+    assert_eq!(expand(&s.replace("{{{1|}}}", "c")), "y");
+    assert_eq!(expand(&s.replace("{{{1|}}}", "d")), "z");
+    assert_eq!(expand("{{#switch: d | a = x | y }}"), "y");
+    assert_eq!(expand("{{#switch: d | a = x }}"), "");
+    assert_eq!(expand("{{#switch: 1.0 | 1 = x | y }}"), "x");
+    assert_eq!(expand("{{#switch: a | a = [[b|c]] }}"), "[[b|c]]");
+    assert_eq!(expand("{{#switch: x | #default | a = b }}"), "b");
+}
+
+#[test]
+fn test_expr() {
+    assert_eq!(expand("{{#expr: 1 + 2 * 3}}"), "7");
+    assert_eq!(expand("{{#expr: (1 + 2) * 3}}"), "9");
+    assert_eq!(expand("{{#expr: 2^3^2}}"), "64");
+    assert_eq!(expand("{{#expr: -2^2}}"), "4");
+    assert_eq!(expand("{{#expr: 1/3}}"), "0.33333333333333");
+    assert_eq!(expand("{{#expr: 7 mod 3}}"), "1");
+    assert_eq!(expand("{{#expr: 7.5 fmod 2}}"), "1.5");
+    assert_eq!(expand("{{#expr: 2.5 round 0}}"), "3");
+    assert_eq!(expand("{{#expr: 1.2345 round 2}}"), "1.23");
+    assert_eq!(expand("{{#expr: 3 < 4 and not 0}}"), "1");
+    assert_eq!(expand("{{#expr: 1 <> 1 or 2 != 2}}"), "0");
+    assert_eq!(expand("{{#expr: 1e3}}"), "1000");
+    assert_eq!(expand("{{#expr: trunc 2.7 + floor -1.5 + ceil 0.2}}"), "1");
+    assert_eq!(expand("{{#expr: abs -3 * sqrt 4}}"), "6");
+    assert_eq!(expand("{{#expr: 10^20}}"), "1.0E+20");
+    assert_eq!(expand("{{#expr: pi}}"), "3.1415926535898");
+    assert_eq!(expand("{{#expr: }}"), "");
+    assert_eq!(
+        expand("{{#expr: 1/0}}"),
+        "<strong class=\"error\">Division by zero.</strong>"
+    );
+    assert_eq!(
+        expand("{{#expr: 1 +}}"),
+        "<strong class=\"error\">Expression error: Missing operand for +.</strong>"
+    );
+    assert_eq!(
+        expand("{{#expr: (1}}"),
+        "<strong class=\"error\">Expression error: Unclosed bracket.</strong>"
+    );
+    assert_eq!(
+        expand("{{#expr: 1)}}"),
+        "<strong class=\"error\">Expression error: Unexpected closing bracket.</strong>"
+    );
+    assert_eq!(
+        expand("{{#expr: 1 2}}"),
+        "<strong class=\"error\">Expression error: Unexpected number.</strong>"
+    );
+    assert_eq!(
+        expand("{{#expr: a}}"),
+        "<strong class=\"error\">Expression error: Unrecognized word \"a\".</strong>"
+    );
+    assert_eq!(
+        expand("{{#expr: 1 ? 2}}"),
+        "<strong class=\"error\">Expression error: Unrecognized punctuation character \"?\".</strong>"
+    );
+}
+
+#[test]
+fn test_ifexpr() {
+    assert_eq!(expand("{{#ifexpr: 1 > 0 | a | b }}"), "a");
+    assert_eq!(expand("{{#ifexpr: 1 < 0 | a | b }}"), "b");
+    assert_eq!(expand("{{#ifexpr: | a | b }}"), "b");
+    assert_eq!(
+        expand("{{#ifexpr: 1/0 | a | b }}"),
+        "<strong class=\"error\">Division by zero.</strong>"
+    );
+}
+
+#[test]
+fn test_tag() {
+    assert_eq!(
+        expand("{{#tag: ref | {{echo|a}} | name = \"b\" | group=c }}"),
+        "<ref name=\"b\" group=\"c\">a</ref>"
+    );
+    assert_eq!(expand("{{#tag: br }}"), "<br />");
+    let output = Configuration::default()
+        .expand(
+            "{{#tag:nowiki|''a''}}",
+            &ExpansionContext {
+                functions: &ParserFunctions::new(),
+                page: &PAGE,
+                templates: &HashMap::<String, String>::new(),
+            },
+        )
+        .unwrap()
+        .output;
+    assert!(matches!(
+        output.nodes.as_slice(),
+        [parse_wiki_text_2::Node::Tag { name, .. }] if name == "nowiki"
+    ));
+}
+
+#[test]
+fn test_registered_function() {
+    let mut functions = ParserFunctions::new();
+    functions.register("#count", |arguments| arguments.len().to_string());
+    functions.register("#if", |_| "replaced".to_string());
+    let context = ExpansionContext {
+        functions: &functions,
+        page: &PAGE,
+        templates: &HashMap::<String, String>::new(),
+    };
+    let expand = |wiki_text| {
+        Configuration::default()
+            .expand(wiki_text, &context)
+            .unwrap()
+            .wiki_text
+    };
+    assert_eq!(expand("{{#count: a | {{#count:}} | c }}"), "3");
+    assert_eq!(expand("{{#if: a | b }}"), "replaced");
+    assert_eq!(expand("{{#unknown: {{#count: a}} }}"), "{{#unknown: 1 }}");
+    assert_eq!(expand("{{lc: A }}"), "{{lc: A }}");
+}
@@ -0,0 +1,50 @@
+use parse_wiki_text_2::{Configuration, Page};
+
+#[test]
+fn test_plain_text() {
+    let s = concat!(
+        "{{Infobox|a=b}}'''Alpha''' is a [[beta]] of [[gamma|delta]]s&nbsp;&amp; [http://epsilon zeta].<!-- eta -->\n",
+        "\n",
+        "==Theta==\n",
+        "Iota<ref>kappa</ref> <nowiki>''lambda''</nowiki>\n",
+        "* Mu\n",
+        "* Nu\n",
+        "{|\n",
+        "|+ Xi\n",
+        "| class=omicron | Pi || Rho\n",
+        "|}\n",
+        "[[File:sigma.png|thumb|tau]]\n",
+        "[[Category:upsilon]]",
+    );
+    let output = Configuration::default().parse(s).unwrap();
+    assert_eq!(
+        output.to_plain_text(),
+        "Alpha is a beta of deltas\u{a0}& zeta.\n\nTheta\nIota ''lambda''\nMu\nNu\nXi\nPi\nRho"
+    );
+}
+
+#[test]
+fn test_plain_text_with_page() {
+    let output = Configuration::default()
+        .parse("'''{{PAGENAME}}''' ({{CURRENTYEAR}}) {{Infobox}}")
+        .unwrap();
+    assert_eq!(output.to_plain_text(), "()");
+    let page = Page {
+        current_time: 1_234_567_890,
+        namespace: "",
+        page_name: "Alpha",
+    };
+    assert_eq!(output.to_plain_text_with_page(&page), "Alpha (2009)");
+}
+
+#[test]
+fn test_pipe_trick() {
+    let output = Configuration::default()
+        .parse("[[Help:Alpha (beta)|]], [[w:Gamma, delta|]]s, [[Epsilon (zeta), eta|]] [[:Theta|]]")
+        .unwrap();
+    assert_eq!(output.to_plain_text(), "Alpha, Gammas, Epsilon Theta");
+    let output = Configuration::default()
+        .parse("[[Iota (kappa, lambda)|]] [[Mu, nu (xi)|]]")
+        .unwrap();
+    assert_eq!(output.to_plain_text(), "Iota Mu");
+}
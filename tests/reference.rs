@@ -0,0 +1,126 @@
+use parse_wiki_text_2::{Configuration, References, WarningMessage};
+
+fn references(wiki_text: &str) -> References {
+    Configuration::default()
+        .parse(wiki_text)
+        .unwrap()
+        .references(wiki_text)
+}
+
+fn footnotes(references: &References) -> Vec<(&str, usize, Option<&str>)> {
+    references
+        .footnotes
+        .iter()
+        .map(|footnote| {
+            (
+                footnote.group.as_str(),
+                footnote.number,
+                footnote.content.as_deref(),
+            )
+        })
+        .collect()
+}
+
+fn warnings(references: &References) -> Vec<(usize, WarningMessage)> {
+    references
+        .warnings
+        .iter()
+        .map(|warning| (warning.start, warning.message))
+        .collect()
+}
+
+#[test]
+fn test_reuse() {
+    let references = references(
+        "a<ref name=\"x\"/> b<ref name=\"x\"> alpha </ref> c<ref>beta</ref> d<ref name=x />",
+    );
+    assert_eq!(
+        footnotes(&references),
+        [("", 1, Some("alpha")), ("", 2, Some("beta"))]
+    );
+    let footnote_indices: Vec<_> = references
+        .references
+        .iter()
+        .map(|reference| reference.footnote)
+        .collect();
+    assert_eq!(footnote_indices, [Some(0), Some(0), Some(1), Some(0)]);
+    assert_eq!(references.references[1].name.as_deref(), Some("x"));
+    assert!(references.warnings.is_empty());
+}
+
+#[test]
+fn test_groups() {
+    let references = references(
+        "<ref>a</ref><ref group=note>b</ref><ref>c</ref><references/><ref>d</ref><ref group=note>e</ref>",
+    );
+    assert_eq!(
+        footnotes(&references),
+        [
+            ("", 1, Some("a")),
+            ("note", 1, Some("b")),
+            ("", 2, Some("c")),
+            ("", 1, Some("d")),
+            ("note", 2, Some("e")),
+        ]
+    );
+}
+
+#[test]
+fn test_list_defined() {
+    let references = references(
+        "a<ref name=x/> b<ref group=g name=y/>\n<references>\n<ref name=x>alpha</ref>\n<ref name=z>gamma</ref>\n</references>\n<references group=g>\n<ref name=y>beta</ref>\n</references>",
+    );
+    assert_eq!(
+        footnotes(&references),
+        [("", 1, Some("alpha")), ("g", 1, Some("beta"))]
+    );
+    let list_defined: Vec<_> = references
+        .references
+        .iter()
+        .map(|reference| {
+            (
+                reference.group.as_str(),
+                reference.list_defined,
+                reference.footnote,
+            )
+        })
+        .collect();
+    assert_eq!(
+        list_defined,
+        [
+            ("", false, Some(0)),
+            ("g", false, Some(1)),
+            ("", true, Some(0)),
+            ("", true, None),
+            ("g", true, Some(1)),
+        ]
+    );
+    assert_eq!(
+        warnings(&references),
+        [(75, WarningMessage::UnusedReference)]
+    );
+}
+
+#[test]
+fn test_follow() {
+    let references =
+        references("<ref name=p>alpha</ref> <ref follow=p>beta</ref> <ref follow=q>gamma</ref>");
+    assert_eq!(
+        footnotes(&references),
+        [("", 1, Some("alpha")), ("", 2, Some("gamma"))]
+    );
+    assert_eq!(references.footnotes[0].continuations, ["beta"]);
+    assert_eq!(references.references[1].follow.as_deref(), Some("p"));
+}
+
+#[test]
+fn test_warnings() {
+    let references = references("<ref name=x>alpha</ref> <ref name=x>beta</ref> <ref name=y/>");
+    assert_eq!(
+        warnings(&references),
+        [
+            (24, WarningMessage::ConflictingReference),
+            (47, WarningMessage::UndefinedReference),
+        ]
+    );
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "serde")]
+
+use parse_wiki_text_2::{Configuration, Output};
+
+#[test]
+fn test_serialize() {
+    let output = Configuration::default().parse("''a''").unwrap();
+    assert_eq!(
+        serde_json::to_string(&output).unwrap(),
+        r#"{"nodes":[{"type":"Italic","end":2,"start":0},{"type":"Text","end":3,"start":2,"value":"a"},{"type":"Italic","end":5,"start":3}],"warnings":[]}"#
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let s = "#REDIRECT [[a]]\n==b==\n* [[File:c.png|thumb|d]] {{e|f=<span class='g'>h</span>}}\n{|\n|i\n|}\n</j>";
+    let output = Configuration::default().parse(s).unwrap();
+    let json = serde_json::to_string(&output).unwrap();
+    let deserialized: Output = serde_json::from_str(&json).unwrap();
+    assert_eq!(format!("{:?}", deserialized), format!("{:?}", output));
+}
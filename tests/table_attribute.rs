@@ -0,0 +1,113 @@
+use parse_wiki_text_2::{
+    AttributeQuote, Configuration, ExpansionContext, Node, Page, ParserFunctions, TableAttribute,
+    TableAttributeValue,
+};
+use std::{borrow::Cow, collections::HashMap};
+
+fn table_attributes<'a>(nodes: &'a [Node<'a>]) -> &'a [TableAttribute<'a>] {
+    match nodes {
+        [Node::Table { attributes, .. }] => attributes,
+        _ => panic!("{:?}", nodes),
+    }
+}
+
+#[test]
+fn test_spans() {
+    let wiki_text = "{| class = \"wikitable\" data-{{alpha}}=beta style='{{gamma}}'\n|}";
+    let output = Configuration::default().parse(wiki_text).unwrap();
+    let spans: Vec<_> = table_attributes(&output.nodes)
+        .iter()
+        .map(|attribute| {
+            (
+                &wiki_text[attribute.start..attribute.end],
+                attribute.name.as_ref(),
+                attribute
+                    .value
+                    .as_ref()
+                    .map(|value| &wiki_text[value.start..value.end]),
+            )
+        })
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            ("class = \"wikitable\"", "class", Some("wikitable")),
+            ("data-{{alpha}}=beta", "data-{{alpha}}", Some("beta")),
+            ("style='{{gamma}}'", "style", Some("{{gamma}}")),
+        ]
+    );
+}
+
+#[test]
+fn test_class_names() {
+    let wiki_text =
+        "{| CLASS=\" wikitable  sortable{{alpha}} {{beta}}gamma delta\" title=\"a b\"\n|}";
+    let output = Configuration::default().parse(wiki_text).unwrap();
+    let attributes = table_attributes(&output.nodes);
+    assert_eq!(attributes[0].class_names(), ["wikitable", "delta"]);
+    assert!(attributes[1].class_names().is_empty());
+}
+
+#[test]
+fn test_modified_attributes() {
+    let wiki_text = "{| class=wikitable <!-- alpha -->\n| colspan=\"2\" | beta\n|}";
+    let mut output = Configuration::default().parse(wiki_text).unwrap();
+    assert_eq!(output.to_wikitext(wiki_text), wiki_text);
+    let Node::Table {
+        attributes, rows, ..
+    } = &mut output.nodes[0]
+    else {
+        panic!("{:?}", output.nodes);
+    };
+    assert_eq!(attributes.len(), 1);
+    attributes.push(TableAttribute {
+        end: 0,
+        name: Cow::Borrowed("style"),
+        name_nodes: vec![],
+        start: 0,
+        value: Some(TableAttributeValue {
+            end: 0,
+            nodes: vec![Node::Text {
+                end: 0,
+                start: 0,
+                value: Cow::Borrowed("color: red"),
+            }],
+            quote: AttributeQuote::Double,
+            start: 0,
+        }),
+    });
+    rows[0].cells[0].attributes.as_mut().unwrap()[0].value = None;
+    // The code around the new attribute, including the comment, can't be kept, so it's replaced by the default syntax.
+    assert_eq!(
+        output.to_wikitext(wiki_text),
+        "{| class=wikitable style=\"color: red\"\n|-\n| colspan | beta\n|}"
+    );
+}
+
+#[test]
+fn test_expanded_attributes() {
+    let templates: HashMap<String, String> = [("Alpha", "class=\"wikitable\""), ("Beta", "2")]
+        .iter()
+        .map(|(name, wiki_text)| (name.to_string(), wiki_text.to_string()))
+        .collect();
+    let context = ExpansionContext {
+        functions: &ParserFunctions::new(),
+        page: &Page {
+            current_time: 0,
+            namespace: "",
+            page_name: "Test",
+        },
+        templates: &templates,
+    };
+    let expansion = Configuration::default()
+        .expand("{| {{alpha}}\n| colspan={{beta}} | gamma\n|}", &context)
+        .unwrap();
+    assert_eq!(
+        expansion.wiki_text,
+        "{| class=\"wikitable\"\n| colspan=2 | gamma\n|}"
+    );
+    assert_eq!(
+        table_attributes(&expansion.output.nodes)[0].class_names(),
+        ["wikitable"]
+    );
+}
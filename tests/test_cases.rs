@@ -2,7 +2,9 @@ mod to_test_str;
 
 use to_test_str::ToTestStr;
 
-use parse_wiki_text_2::Configuration;
+use parse_wiki_text_2::{
+    Configuration, ConfigurationSource, IncludeMode, MagicLinkType, ParseOptions,
+};
 use std::fs;
 
 struct Case {
@@ -76,14 +78,17 @@ fn write_cases(cases: &[Case]) -> String {
 }
 
 fn test_file(path: &str) {
+    test_file_with_options(path, &Configuration::default(), &ParseOptions::default())
+}
+
+fn test_file_with_options(path: &str, cfg: &Configuration, options: &ParseOptions) {
     let content = fs::read_to_string(path).expect("could not read file");
     let mut cases = parse_cases(&content);
     let mut changed_cases = false;
 
-    let cfg = Configuration::default();
-
     for case in &mut cases {
-        let res = cfg.parse(&case.case).unwrap();
+        let res = cfg.parse_with_options(&case.case, options).unwrap();
+        assert_eq!(res.to_wikitext(&case.case), case.case);
         let expected_nodes = res.nodes.to_test_str();
         let expected_warnings = res.warnings.to_test_str();
 
@@ -126,9 +131,13 @@ test_files![
     character_entity,
     comment,
     external_link,
+    function,
+    gallery,
     heading,
     horizontal_divider,
+    inclusion,
     invalid_character,
+    language_link,
     link,
     list,
     magic_word,
@@ -140,9 +149,58 @@ test_files![
     redirect,
     table,
     tag,
-    template
+    template,
+    variable
 ];
 
+#[test]
+fn bare_url() {
+    test_file_with_options(
+        "./tests/cases/bare_url.test",
+        &Configuration::default(),
+        &ParseOptions {
+            bare_urls: true,
+            ..ParseOptions::default()
+        },
+    )
+}
+
+#[test]
+fn magic_link() {
+    test_file_with_options(
+        "./tests/cases/magic_link.test",
+        &Configuration::new(&ConfigurationSource {
+            capital_links: true,
+            category_namespaces: &[],
+            extension_tags: &[],
+            file_namespaces: &[],
+            image_options: &[],
+            interwiki: &[],
+            link_prefix: "",
+            link_trail: "abcdefghijklmnopqrstuvwxyz",
+            magic_links: &[MagicLinkType::Isbn, MagicLinkType::Pmid, MagicLinkType::Rfc],
+            magic_words: &[],
+            namespaces: &[],
+            protocols: &["https://"],
+            redirect_magic_words: &[],
+            variables: &[],
+        }),
+        &ParseOptions::default(),
+    )
+}
+
+#[test]
+fn transclusion() {
+    test_file_with_options(
+        "./tests/cases/transclusion.test",
+        &Configuration::default(),
+        &ParseOptions {
+            include_mode: IncludeMode::Transclusion,
+            ..ParseOptions::default()
+        },
+    )
+}
+
 // #[test]
 // fn write_test_cases() {
 // 	let cfg = Configuration::default();
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use parse_wiki_text_2::{Configuration, FunctionParameter, Node, Output, Parameter};
+    use parse_wiki_text_2::{
+        Attribute, AttributeQuote, AttributeValue, Configuration, FunctionParameter, ListItem,
+        Node, Output, Parameter,
+    };
 
     #[test]
     fn test_1() {
@@ -13,7 +16,7 @@ mod tests {
                 name: vec![Node::Text {
                     start: 2,
                     end: 3,
-                    value: "a",
+                    value: "a".into(),
                 }],
                 parameters: vec![],
             }],
@@ -33,7 +36,7 @@ mod tests {
                 name: vec![Node::Text {
                     start: 2,
                     end: 3,
-                    value: "a",
+                    value: "a".into(),
                 }],
                 parameters: vec![Parameter {
                     start: 4,
@@ -59,7 +62,7 @@ mod tests {
                 name: vec![Node::Text {
                     start: 2,
                     end: 3,
-                    value: "a",
+                    value: "a".into(),
                 }],
                 parameters: vec![Parameter {
                     start: 4,
@@ -68,7 +71,7 @@ mod tests {
                     value: vec![Node::Text {
                         start: 4,
                         end: 5,
-                        value: "b",
+                        value: "b".into(),
                     }],
                 }],
             }],
@@ -88,7 +91,7 @@ mod tests {
                 name: vec![Node::Text {
                     start: 2,
                     end: 3,
-                    value: "a",
+                    value: "a".into(),
                 }],
                 parameters: vec![
                     Parameter {
@@ -98,7 +101,7 @@ mod tests {
                         value: vec![Node::Text {
                             start: 4,
                             end: 5,
-                            value: "b",
+                            value: "b".into(),
                         }],
                     },
                     Parameter {
@@ -108,7 +111,7 @@ mod tests {
                         value: vec![Node::Text {
                             start: 6,
                             end: 7,
-                            value: "c",
+                            value: "c".into(),
                         }],
                     },
                 ],
@@ -129,18 +132,18 @@ mod tests {
                 name: vec![
                     Node::Parameter {
                         start: 2,
-                        end: 6,
+                        end: 9,
                         name: vec![Node::Text {
                             start: 5,
                             end: 6,
-                            value: "a",
+                            value: "a".into(),
                         }],
                         default: None,
                     },
                     Node::Text {
                         start: 9,
                         end: 10,
-                        value: "a",
+                        value: "a".into(),
                     },
                 ],
                 parameters: vec![
@@ -151,7 +154,7 @@ mod tests {
                         value: vec![Node::Text {
                             start: 11,
                             end: 12,
-                            value: "b",
+                            value: "b".into(),
                         }],
                     },
                     Parameter {
@@ -161,7 +164,7 @@ mod tests {
                         value: vec![Node::Text {
                             start: 13,
                             end: 14,
-                            value: "c",
+                            value: "c".into(),
                         }],
                     },
                 ],
@@ -183,7 +186,7 @@ mod tests {
                 name: vec![Node::Text {
                     start: 2,
                     end: 3,
-                    value: "a",
+                    value: "a".into(),
                 }],
                 parameters: vec![FunctionParameter {
                     start: 4,
@@ -208,7 +211,7 @@ mod tests {
                 name: vec![Node::Text {
                     start: 2,
                     end: 3,
-                    value: "a",
+                    value: "a".into(),
                 }],
                 parameters: vec![FunctionParameter {
                     start: 4,
@@ -216,7 +219,7 @@ mod tests {
                     value: vec![Node::Text {
                         start: 4,
                         end: 5,
-                        value: "b",
+                        value: "b".into(),
                     }],
                 }],
             }],
@@ -236,7 +239,7 @@ mod tests {
                 name: vec![Node::Text {
                     start: 2,
                     end: 3,
-                    value: "a",
+                    value: "a".into(),
                 }],
                 parameters: vec![
                     FunctionParameter {
@@ -245,7 +248,7 @@ mod tests {
                         value: vec![Node::Text {
                             start: 4,
                             end: 5,
-                            value: "b",
+                            value: "b".into(),
                         }],
                     },
                     FunctionParameter {
@@ -254,7 +257,7 @@ mod tests {
                         value: vec![Node::Text {
                             start: 6,
                             end: 7,
-                            value: "c",
+                            value: "c".into(),
                         }],
                     },
                 ],
@@ -275,18 +278,18 @@ mod tests {
                 name: vec![
                     Node::Parameter {
                         start: 2,
-                        end: 6,
+                        end: 9,
                         name: vec![Node::Text {
                             start: 5,
                             end: 6,
-                            value: "a",
+                            value: "a".into(),
                         }],
                         default: None,
                     },
                     Node::Text {
                         start: 9,
                         end: 10,
-                        value: "a",
+                        value: "a".into(),
                     },
                 ],
                 parameters: vec![
@@ -296,7 +299,7 @@ mod tests {
                         value: vec![Node::Text {
                             start: 11,
                             end: 12,
-                            value: "b",
+                            value: "b".into(),
                         }],
                     },
                     FunctionParameter {
@@ -305,7 +308,7 @@ mod tests {
                         value: vec![Node::Text {
                             start: 13,
                             end: 14,
-                            value: "c",
+                            value: "c".into(),
                         }],
                     },
                 ],
@@ -315,4 +318,89 @@ mod tests {
         let actual = c.parse(s).unwrap();
         assert_eq!(format!("{:?}", actual), format!("{:?}", expected));
     }
+
+    #[test]
+    fn test_a1() {
+        let c = Configuration::default();
+        let s = "<span class='a' hidden>";
+        let expected = Output {
+            nodes: vec![Node::StartTag {
+                attributes: vec![
+                    Attribute {
+                        end: 15,
+                        name: "class".into(),
+                        start: 6,
+                        value: Some(AttributeValue {
+                            end: 14,
+                            quote: AttributeQuote::Single,
+                            start: 13,
+                            value: "a".into(),
+                        }),
+                    },
+                    Attribute {
+                        end: 22,
+                        name: "hidden".into(),
+                        start: 16,
+                        value: None,
+                    },
+                ],
+                end: 23,
+                name: "span".into(),
+                start: 0,
+            }],
+            warnings: vec![],
+        };
+        let actual = c.parse(s).unwrap();
+        assert_eq!(format!("{:?}", actual), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn test_p1() {
+        let c = Configuration::default();
+        let s = " a";
+        let expected = Output {
+            nodes: vec![Node::Preformatted {
+                start: 1,
+                end: 2,
+                nodes: vec![Node::Text {
+                    start: 1,
+                    end: 2,
+                    value: "a".into(),
+                }],
+            }],
+            warnings: vec![],
+        };
+        let actual = c.parse(s).unwrap();
+        assert_eq!(format!("{:?}", actual), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn test_l1() {
+        let c = Configuration::default();
+        let s = "* ";
+        let expected = Output {
+            nodes: vec![Node::UnorderedList {
+                start: 0,
+                end: 2,
+                items: vec![ListItem {
+                    start: 1,
+                    end: 2,
+                    nodes: vec![],
+                }],
+            }],
+            warnings: vec![],
+        };
+        let actual = c.parse(s).unwrap();
+        assert_eq!(format!("{:?}", actual), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn test_into_owned() {
+        let c = Configuration::default();
+        let s = String::from("[[File:a.png|thumb|b]] {{c|d=<span class=e>[[f]]g</span>}}");
+        let expected = format!("{:?}", c.parse(&s).unwrap());
+        let actual: Output<'static> = c.parse(&s).unwrap().into_owned();
+        drop(s);
+        assert_eq!(format!("{:?}", actual), expected);
+    }
 }
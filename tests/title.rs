@@ -0,0 +1,88 @@
+use parse_wiki_text_2::{Configuration, Title};
+
+#[test]
+fn test_normalization() {
+    let configuration = Configuration::default();
+    let title = configuration.title(" foo__bar&amp;baz ").unwrap();
+    assert_eq!(
+        title,
+        Title {
+            fragment: None,
+            interwiki: None,
+            namespace: 0,
+            namespace_name: String::new(),
+            page_name: "Foo bar&baz".into(),
+        }
+    );
+    assert_eq!(
+        configuration.title("Foo bar"),
+        configuration.title("foo_bar")
+    );
+}
+
+#[test]
+fn test_namespaces() {
+    let configuration = Configuration::default();
+    let title = configuration.title("image:Example.png").unwrap();
+    assert_eq!(title.namespace, 6);
+    assert_eq!(title.to_string(), "File:Example.png");
+    let title = configuration.title("USER_TALK : foo").unwrap();
+    assert_eq!(title.namespace, 3);
+    assert_eq!(title.to_string(), "User talk:Foo");
+    let title = configuration.title("Unknown:foo").unwrap();
+    assert_eq!(title.namespace, 0);
+    assert_eq!(title.page_name, "Unknown:foo");
+}
+
+#[test]
+fn test_fragment() {
+    let configuration = Configuration::default();
+    let title = configuration.title("help:contents # Editing").unwrap();
+    assert_eq!(title.page_name, "Contents");
+    assert_eq!(title.fragment.as_deref(), Some("Editing"));
+    assert_eq!(title.to_string(), "Help:Contents#Editing");
+    let title = configuration.title("#Section").unwrap();
+    assert_eq!(title.page_name, "");
+    assert_eq!(title.to_string(), "#Section");
+}
+
+#[test]
+fn test_interwiki() {
+    let configuration = Configuration::default();
+    let title = configuration.title("FR:paris#Histoire").unwrap();
+    assert_eq!(title.interwiki.as_deref(), Some("fr"));
+    assert_eq!(title.namespace, 0);
+    assert_eq!(title.page_name, "paris");
+    assert_eq!(title.to_string(), "fr:paris#Histoire");
+    let title = configuration.title("wikt:").unwrap();
+    assert_eq!(title.interwiki.as_deref(), Some("wikt"));
+    assert_eq!(title.page_name, "");
+}
+
+#[test]
+fn test_invalid_titles() {
+    let configuration = Configuration::default();
+    assert_eq!(configuration.title("a[b"), None);
+    assert_eq!(configuration.title("a|b"), None);
+    assert_eq!(configuration.title("Category:"), None);
+    assert_eq!(configuration.title(" "), None);
+}
+
+#[test]
+fn test_template_titles() {
+    let configuration = Configuration::default();
+    let title = configuration.template_title("foo").unwrap();
+    assert_eq!(title.namespace, 10);
+    assert_eq!(title.to_string(), "Template:Foo");
+    assert_eq!(
+        configuration.template_title(":foo").unwrap().to_string(),
+        "Foo"
+    );
+    assert_eq!(
+        configuration
+            .template_title("user:foo")
+            .unwrap()
+            .to_string(),
+        "User:Foo"
+    );
+}
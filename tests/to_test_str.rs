@@ -1,4 +1,7 @@
-use parse_wiki_text_2::{DefinitionListItem, FunctionParameter, ListItem, Node, Parameter, TableCaption, TableCell, TableRow, Warning};
+use parse_wiki_text_2::{
+    Attribute, AttributeValue, DefinitionListItem, FunctionParameter, GalleryEntry, ImageOption,
+    ListItem, Node, Parameter, TableAttribute, TableCaption, TableCell, TableRow, Warning,
+};
 
 pub trait ToTestStr {
     fn to_test_str(&self) -> String;
@@ -28,9 +31,7 @@ where
     T: ToTestStr,
 {
     fn to_test_str(&self) -> String {
-        self.as_ref()
-            .map(|t| t.to_test_str())
-            .unwrap_or_default()
+        self.as_ref().map(|t| t.to_test_str()).unwrap_or_default()
     }
 }
 
@@ -50,30 +51,71 @@ impl ToTestStr for Node<'_> {
                 format!("CharacterEntity({character})")
             }
             Comment { .. } => "Comment".into(),
+            Ignored { .. } => "Ignored".into(),
             DefinitionList { items, .. } => {
                 format!("DefinitionList({})", items.to_test_str())
             }
             EndTag { name, .. } => format!("EndTag({name})"),
-            ExternalLink { nodes, .. } => {
-                format!("ExternalLink({})", nodes.to_test_str())
-            }
-            Function { name, parameters, .. } => format!(
+            ExternalLink {
+                bracketed: true,
+                label,
+                url,
+                ..
+            } => format!("ExternalLink({url}, {})", label.to_test_str()),
+            ExternalLink { url, .. } => format!("BareUrl({url})"),
+            Function {
+                name, parameters, ..
+            } => format!(
                 "Function({}, {})",
                 name.to_test_str(),
                 parameters.to_test_str()
             ),
+            Gallery {
+                attributes,
+                entries,
+                ..
+            } => {
+                if attributes.is_empty() {
+                    format!("Gallery({})", entries.to_test_str())
+                } else {
+                    format!(
+                        "Gallery({}, {})",
+                        attributes.to_test_str(),
+                        entries.to_test_str()
+                    )
+                }
+            }
             Heading { level, nodes, .. } => {
                 format!("Heading({level}, {})", nodes.to_test_str())
             }
             HorizontalDivider { .. } => "HorizontalDivider".into(),
-            Image { target, text, .. } => {
-                format!("Image({target}, {})", text.to_test_str())
+            Image {
+                options, target, ..
+            } => {
+                format!("Image({target}, {})", options.to_test_str())
             }
             Italic { .. } => "Italic".into(),
-            Link { target, text, .. } => {
-                format!("Link({target}, {})", text.to_test_str())
+            LanguageLink { target, text, .. } => {
+                format!("LanguageLink({target}, {})", text.to_test_str())
+            }
+            Link {
+                prefix,
+                target,
+                text,
+                trail,
+                ..
+            } => {
+                let mut s = format!("Link({target}, {}", text.to_test_str());
+                if let Some(prefix) = prefix {
+                    s += &format!(", prefix: {}", prefix.value);
+                }
+                if let Some(trail) = trail {
+                    s += &format!(", trail: {}", trail.value);
+                }
+                s + ")"
             }
-            MagicWord { .. } => "MagicWord".into(),
+            MagicLink { id, type_, .. } => format!("MagicLink({type_:?}, {id})"),
+            MagicWord { id, .. } => format!("MagicWord({id})"),
             OrderedList { items, .. } => {
                 format!("OrderedList({})", items.to_test_str())
             }
@@ -87,7 +129,15 @@ impl ToTestStr for Node<'_> {
                 format!("Preformatted({})", nodes.to_test_str())
             }
             Redirect { target, .. } => format!("Redirect({target})"),
-            StartTag { name, .. } => format!("StartTag({name})"),
+            StartTag {
+                attributes, name, ..
+            } => {
+                if attributes.is_empty() {
+                    format!("StartTag({name})")
+                } else {
+                    format!("StartTag({name}, {})", attributes.to_test_str())
+                }
+            }
             Table {
                 attributes,
                 captions,
@@ -101,8 +151,22 @@ impl ToTestStr for Node<'_> {
                     rows.to_test_str()
                 )
             }
-            Tag { name, nodes, .. } => {
-                format!("Tag({name}, {})", nodes.to_test_str())
+            Tag {
+                attributes,
+                name,
+                nodes,
+                raw_content,
+                ..
+            } => {
+                let content = match raw_content {
+                    None => nodes.to_test_str(),
+                    Some(raw_content) => format!("Raw({})", raw_content.value),
+                };
+                if attributes.is_empty() {
+                    format!("Tag({name}, {content})")
+                } else {
+                    format!("Tag({name}, {}, {content})", attributes.to_test_str())
+                }
             }
             Template {
                 name, parameters, ..
@@ -115,10 +179,48 @@ impl ToTestStr for Node<'_> {
             UnorderedList { items, .. } => {
                 format!("UnorderedList({})", items.to_test_str())
             }
+            Variable { id, name, .. } => format!("Variable({id}, {name})"),
+        }
+    }
+}
+
+impl ToTestStr for Attribute<'_> {
+    fn to_test_str(&self) -> String {
+        match &self.value {
+            None => format!("Attribute({})", self.name),
+            Some(value) => format!("Attribute({}, {})", self.name, value.to_test_str()),
         }
     }
 }
 
+impl ToTestStr for AttributeValue<'_> {
+    fn to_test_str(&self) -> String {
+        format!("{:?}({})", self.quote, self.value)
+    }
+}
+
+impl ToTestStr for GalleryEntry<'_> {
+    fn to_test_str(&self) -> String {
+        format!(
+            "GalleryEntry({}, {})",
+            self.title
+                .as_ref()
+                .map_or(self.target.to_string(), ToString::to_string),
+            self.options.to_test_str()
+        )
+    }
+}
+
+impl ToTestStr for ImageOption<'_> {
+    fn to_test_str(&self) -> String {
+        format!(
+            "ImageOption({:?}, {})",
+            self.type_,
+            self.value.to_test_str()
+        )
+    }
+}
+
 impl ToTestStr for ListItem<'_> {
     fn to_test_str(&self) -> String {
         format!("ListItem({})", self.nodes.to_test_str())
@@ -141,6 +243,25 @@ impl ToTestStr for Warning {
     }
 }
 
+impl ToTestStr for TableAttribute<'_> {
+    fn to_test_str(&self) -> String {
+        let name = if self.name_nodes.is_empty() {
+            self.name.to_string()
+        } else {
+            self.name_nodes.to_test_str()
+        };
+        match &self.value {
+            None => format!("Attribute({})", name),
+            Some(value) => format!(
+                "Attribute({}, {:?}({}))",
+                name,
+                value.quote,
+                value.nodes.to_test_str()
+            ),
+        }
+    }
+}
+
 impl ToTestStr for TableCaption<'_> {
     fn to_test_str(&self) -> String {
         format!(
@@ -184,9 +305,6 @@ impl ToTestStr for Parameter<'_> {
 
 impl ToTestStr for FunctionParameter<'_> {
     fn to_test_str(&self) -> String {
-        format!(
-            "FunctionParameter({})",
-            self.value.to_test_str()
-        )
+        format!("FunctionParameter({})", self.value.to_test_str())
     }
 }
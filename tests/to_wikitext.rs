@@ -0,0 +1,115 @@
+use parse_wiki_text_2::{Configuration, Node, Parameter};
+
+#[test]
+fn test_unmodified() {
+    let s = "==Heading==\n{{a | b = c |d}}\n* [[e|f]]g\n{|\n|h||i\n|}";
+    let output = Configuration::default().parse(s).unwrap();
+    assert_eq!(output.to_wikitext(s), s);
+}
+
+#[test]
+fn test_modified_parameter() {
+    let s = "x {{a | b = c\n | d = e }} y";
+    let mut output = Configuration::default().parse(s).unwrap();
+    let Node::Template { parameters, .. } = &mut output.nodes[1] else {
+        panic!("expected template");
+    };
+    let Node::Text { value, .. } = &mut parameters[0].value[0] else {
+        panic!("expected text");
+    };
+    *value = "changed".into();
+    assert_eq!(output.to_wikitext(s), "x {{a | b = changed\n | d = e }} y");
+}
+
+#[test]
+fn test_removed_parameter() {
+    let s = "{{a | b = c | d = e }}";
+    let mut output = Configuration::default().parse(s).unwrap();
+    let Node::Template { parameters, .. } = &mut output.nodes[0] else {
+        panic!("expected template");
+    };
+    parameters.remove(0);
+    assert_eq!(output.to_wikitext(s), "{{a | d = e }}");
+}
+
+#[test]
+fn test_added_parameter() {
+    let s = "{{a|b}}";
+    let mut output = Configuration::default().parse(s).unwrap();
+    let Node::Template { parameters, .. } = &mut output.nodes[0] else {
+        panic!("expected template");
+    };
+    parameters.push(Parameter {
+        end: 0,
+        name: Some(vec![Node::Text {
+            end: 0,
+            start: 0,
+            value: "c".into(),
+        }]),
+        start: 0,
+        value: vec![Node::Text {
+            end: 0,
+            start: 0,
+            value: "d".into(),
+        }],
+    });
+    assert_eq!(output.to_wikitext(s), "{{a|b|c=d}}");
+}
+
+#[test]
+fn test_created_node() {
+    let node = Node::Link {
+        end: 0,
+        prefix: None,
+        reparsed: false,
+        start: 0,
+        target: "a".into(),
+        text: vec![Node::Text {
+            end: 0,
+            start: 0,
+            value: "b".into(),
+        }],
+        trail: None,
+    };
+    assert_eq!(node.to_wikitext(""), "[[a|b]]");
+    let node = Node::MagicWord {
+        end: 0,
+        id: "NOTOC".into(),
+        start: 0,
+    };
+    assert_eq!(node.to_wikitext(""), "__NOTOC__");
+}
+
+#[test]
+fn test_created_external_link() {
+    let node = Node::ExternalLink {
+        bracketed: true,
+        end: 0,
+        label: vec![Node::Text {
+            end: 0,
+            start: 0,
+            value: "b".into(),
+        }],
+        start: 0,
+        url: "https://a".into(),
+    };
+    assert_eq!(node.to_wikitext(""), "[https://a b]");
+    let s = "[https://a b]";
+    let mut output = Configuration::default().parse(s).unwrap();
+    if let Node::ExternalLink { url, .. } = &mut output.nodes[0] {
+        *url = "//c".into();
+    }
+    assert_eq!(output.to_wikitext(s), "[//c b]");
+}
+
+#[test]
+fn test_modified_magic_word() {
+    let s = "__notoc__ __NOGALLERY__";
+    let mut output = Configuration::default().parse(s).unwrap();
+    assert_eq!(output.to_wikitext(s), s);
+    let Node::MagicWord { id, .. } = &mut output.nodes[2] else {
+        panic!("expected magic word");
+    };
+    *id = "FORCETOC".into();
+    assert_eq!(output.to_wikitext(s), "__notoc__ __FORCETOC__");
+}
@@ -0,0 +1,41 @@
+use parse_wiki_text_2::{Configuration, Node, Visit, VisitMut};
+
+#[test]
+fn test_visit_templates() {
+    struct TemplateNames(Vec<String>);
+
+    impl<'a> Visit<'a> for TemplateNames {
+        fn visit_template(&mut self, node: &Node<'a>) {
+            if let Node::Template { name, .. } = node
+                && let [Node::Text { value, .. }] = name.as_slice()
+            {
+                self.0.push(value.to_string());
+            }
+            self.visit_children(node);
+        }
+    }
+
+    let s = "{| {{x}} class={{y}}\n|{{a|{{b}}}}\n|}\n;c:{{d|e={{f}}}}\n[[File:g.png|{{h}}]]";
+    let output = Configuration::default().parse(s).unwrap();
+    let mut names = TemplateNames(vec![]);
+    names.visit_nodes(&output.nodes);
+    assert_eq!(names.0, ["x", "y", "a", "b", "d", "f", "h"]);
+}
+
+#[test]
+fn test_visit_mut_text() {
+    struct Uppercase;
+
+    impl<'a> VisitMut<'a> for Uppercase {
+        fn visit_text(&mut self, node: &mut Node<'a>) {
+            if let Node::Text { value, .. } = node {
+                *value = value.to_uppercase().into();
+            }
+        }
+    }
+
+    let s = "a ''[[b|c]]'' {{d|e}}";
+    let mut output = Configuration::default().parse(s).unwrap();
+    Uppercase.visit_nodes(&mut output.nodes);
+    assert_eq!(output.to_wikitext(s), "A ''[[b|C]]'' {{D|E}}");
+}